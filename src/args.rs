@@ -0,0 +1,241 @@
+use std::env;
+
+use crate::gui_eng::{MAX_UI_SCALE, MIN_UI_SCALE};
+
+pub const USAGE: &str = "\
+usage: debug [scene.chsl] [options]
+
+options:
+    --width <px>        window width (default 1240)
+    --height <px>       window height (default 880)
+    --bounds <x,y,w,h>  world bounds, also replaces a loaded scene's (default the window size)
+    --fps <n>           frame rate cap, 0 for uncapped (default 60)
+    --idle-fps <n>      frame rate while paused and idle (default 15)
+    --vsync             sync presents to the display
+    --offscreen         render the editor into memory, no display or gpu needed
+    --frames <n>        quit after this many frames
+    --record-frames <p> save rendered frames, a .gif path encodes a gif, anything else is a folder of pngs
+    --record-every <n>  only keep every nth frame when recording (default 1)
+    --ui-scale <x>      size of the UI from 0.5 to 4, detected from the display's dpi by default
+    --font <n>=<p>[:s]  register a font file by name, at point size s (default 128)
+                        a font named numeric replaces the face number fields use
+    --log-timings       print profiler scope timings every frame
+    --chrome-trace <p>  record profiler scopes and write a chrome trace on exit
+    --paused            start with the simulation paused
+    --time-scale <x>    initial simulation time scale (default 1)
+    --headless          step the scene without opening a window
+    --steps <n>         fixed steps to run headless (default 600)
+    --step-size <s>     seconds per fixed step (default 1/60)
+    --substeps <n>      physics updates per fixed step (default 1)
+    --trace             write every step instead of only the final state
+    --output <path>     write the state/trace here instead of stdout
+    --save <path>       save the final world as a .chsl file";
+
+pub struct Args {
+    pub scene: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub bounds: Option<(f64, f64, f64, f64)>,
+    pub fps: Option<f64>,
+    pub idle_fps: f64,
+    pub vsync: bool,
+    pub offscreen: bool,
+    pub frames: Option<usize>,
+    pub record_frames: Option<String>,
+    pub record_every: usize,
+    pub ui_scale: Option<f64>,
+    //name, path and point size
+    pub fonts: Vec<(String, String, u16)>,
+    pub log_timings: bool,
+    pub chrome_trace: Option<String>,
+    pub paused: bool,
+    pub time_scale: f64,
+    pub headless: bool,
+    pub steps: usize,
+    pub step_size: f64,
+    pub substeps: usize,
+    pub trace: bool,
+    pub output: Option<String>,
+    pub save: Option<String>,
+}
+
+impl Args {
+    pub fn parse() -> Result<Self, String> {
+        Self::parse_from(env::args().skip(1))
+    }
+
+    pub fn parse_from<I: Iterator<Item = String>>(mut args: I) -> Result<Self, String> {
+        let mut result = Self {
+            scene: None,
+            width: 1240,
+            height: 880,
+            bounds: None,
+            fps: Some(60.0),
+            idle_fps: 15.0,
+            vsync: false,
+            offscreen: false,
+            frames: None,
+            record_frames: None,
+            record_every: 1,
+            ui_scale: None,
+            fonts: vec![],
+            log_timings: false,
+            chrome_trace: None,
+            paused: false,
+            time_scale: 1.0,
+            headless: false,
+            steps: 600,
+            step_size: 1.0 / 60.0,
+            substeps: 1,
+            trace: false,
+            output: None,
+            save: None,
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--width" => result.width = parse_value(&arg, args.next())?,
+                "--height" => result.height = parse_value(&arg, args.next())?,
+                "--bounds" => result.bounds = Some(parse_bounds(&arg, args.next())?),
+                "--fps" => result.fps = Some(parse_value::<f64>(&arg, args.next())?).filter(|fps| *fps > 0.0),
+                "--idle-fps" => result.idle_fps = parse_value(&arg, args.next())?,
+                "--vsync" => result.vsync = true,
+                "--offscreen" => result.offscreen = true,
+                "--frames" => result.frames = Some(parse_value(&arg, args.next())?),
+                "--record-frames" => result.record_frames = Some(parse_value(&arg, args.next())?),
+                "--record-every" => result.record_every = parse_value(&arg, args.next())?,
+                "--ui-scale" => result.ui_scale = Some(parse_value(&arg, args.next())?),
+                "--font" => result.fonts.push(parse_font(&arg, args.next())?),
+                "--log-timings" => result.log_timings = true,
+                "--chrome-trace" => result.chrome_trace = Some(parse_value(&arg, args.next())?),
+                "--paused" => result.paused = true,
+                "--time-scale" => result.time_scale = parse_value(&arg, args.next())?,
+                "--headless" => result.headless = true,
+                "--trace" => result.trace = true,
+                "--steps" => result.steps = parse_value(&arg, args.next())?,
+                "--step-size" => result.step_size = parse_value(&arg, args.next())?,
+                "--substeps" => result.substeps = parse_value(&arg, args.next())?,
+                "--output" => result.output = Some(parse_value(&arg, args.next())?),
+                "--save" => result.save = Some(parse_value(&arg, args.next())?),
+                _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+                _ if result.scene.is_none() => result.scene = Some(arg),
+                _ => return Err(format!("unexpected argument {}", arg)),
+            }
+        }
+
+        if result.width == 0 || result.height == 0 {
+            return Err("--width and --height must be positive".to_string());
+        }
+
+        //uncapped while idle would be the opposite of what it's for
+        if !result.idle_fps.is_finite() || result.idle_fps <= 0.0 {
+            return Err("--idle-fps must be a positive number".to_string());
+        }
+
+        if result.ui_scale.is_some_and(|scale| !(MIN_UI_SCALE..=MAX_UI_SCALE).contains(&scale)) {
+            return Err(format!("--ui-scale must be between {} and {}", MIN_UI_SCALE, MAX_UI_SCALE));
+        }
+
+        if result.record_every == 0 {
+            return Err("--record-every must be positive".to_string());
+        }
+
+        if !result.step_size.is_finite() || result.step_size <= 0.0 {
+            return Err("--step-size must be a positive number".to_string());
+        }
+
+        Ok(result)
+    }
+
+    //falls back to the window rect so bodies stay on screen
+    pub fn world_bounds(&self) -> (f64, f64, f64, f64) {
+        self.bounds.unwrap_or((0.0, 0.0, self.width as f64, self.height as f64))
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or(format!("{} needs a value", name))?;
+    value.parse().map_err(|_| format!("invalid value {} for {}", value, name))
+}
+
+fn parse_bounds(name: &str, value: Option<String>) -> Result<(f64, f64, f64, f64), String> {
+    let value: String = parse_value(name, value)?;
+    let parts: Vec<f64> = value
+        .split(',')
+        .map(|part| part.trim().parse::<f64>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("invalid value {} for {}", value, name))?;
+
+    match parts[..] {
+        [x, y, width, height] if width > 0.0 && height > 0.0 => Ok((x, y, width, height)),
+        _ => Err(format!("{} expects x,y,width,height", name)),
+    }
+}
+
+fn parse_font(name: &str, value: Option<String>) -> Result<(String, String, u16), String> {
+    let value: String = parse_value(name, value)?;
+    let (font, path) = value
+        .split_once('=')
+        .ok_or(format!("{} expects name=path[:size]", name))?;
+
+    //windows paths have colons of their own, anything after the last one without a separator is a size
+    let (path, size) = match path.rsplit_once(':') {
+        Some((path, size)) if !size.contains(['/', '\\']) => match size.parse::<u16>() {
+            Ok(size) if size > 0 => (path, size),
+            _ => return Err(format!("invalid size {} for {}, expected a positive whole number", size, name)),
+        },
+        _ => (path, 128),
+    };
+
+    if font.is_empty() || path.is_empty() {
+        return Err(format!("{} expects name=path[:size]", name));
+    }
+
+    Ok((font.to_string(), path.to_string(), size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse_from(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn step_size_must_be_a_positive_number() {
+        assert!(parse(&["--step-size", "0.01"]).is_ok());
+        assert!(parse(&["--step-size", "0"]).is_err());
+        assert!(parse(&["--step-size", "NaN"]).is_err());
+        assert!(parse(&["--step-size", "inf"]).is_err());
+    }
+
+    #[test]
+    fn font_sizes_are_parsed() {
+        let font = |value: &str| parse_font("--font", Some(value.to_string()));
+
+        assert_eq!(font("mono=mono.ttf"), Ok(("mono".to_string(), "mono.ttf".to_string(), 128)));
+        assert_eq!(font("mono=mono.ttf:24"), Ok(("mono".to_string(), "mono.ttf".to_string(), 24)));
+        assert_eq!(font("mono=C:\\fonts\\mono.ttf"), Ok(("mono".to_string(), "C:\\fonts\\mono.ttf".to_string(), 128)));
+        assert_eq!(font("mono=C:\\fonts\\mono.ttf:24"), Ok(("mono".to_string(), "C:\\fonts\\mono.ttf".to_string(), 24)));
+        assert!(font("mono=mono.ttf:0").is_err());
+        assert!(font("mono=mono.ttf:abc").is_err());
+        assert!(font("mono=").is_err());
+        assert!(font("mono.ttf").is_err());
+    }
+
+    #[test]
+    fn ui_scale_must_be_in_range() {
+        assert_eq!(parse(&["--ui-scale", "2"]).unwrap().ui_scale, Some(2.0));
+        assert!(parse(&["--ui-scale", "0.25"]).is_err());
+        assert!(parse(&["--ui-scale", "5"]).is_err());
+        assert!(parse(&["--ui-scale", "NaN"]).is_err());
+    }
+
+    #[test]
+    fn idle_fps_must_be_positive() {
+        assert_eq!(parse(&["--idle-fps", "5"]).unwrap().idle_fps, 5.0);
+        assert!(parse(&["--idle-fps", "0"]).is_err());
+        assert!(parse(&["--idle-fps", "-1"]).is_err());
+    }
+}
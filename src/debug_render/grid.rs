@@ -0,0 +1,138 @@
+use chsl::math::vector2::Vector2;
+
+use crate::{gui_eng::scaled, renderer::{font_manager::DEFAULT_FONT, Renderer}};
+
+//screen spacing the minor lines are kept between when zooming
+const MIN_SPACING: f64 = 10.0;
+const RULER_SIZE: i32 = 18;
+//wide enough for a few digits side on
+const SIDE_RULER_SIZE: i32 = 44;
+const LABEL_HEIGHT: i32 = 14;
+
+pub struct GridSettings {
+    pub show_grid: bool,
+    pub show_ruler: bool,
+    //world units between minor lines at 1x zoom
+    pub spacing: f64,
+    pub major_every: usize,
+}
+
+impl GridSettings {
+    pub fn new() -> Self {
+        Self {
+            show_grid: true,
+            show_ruler: true,
+            spacing: 50.0,
+            major_every: 5,
+        }
+    }
+
+    //steps the spacing by the major factor so lines stay readable at any zoom
+    fn adapted_spacing(&self, zoom: f64) -> f64 {
+        let factor = self.major_every.max(2) as f64;
+        let mut spacing = self.spacing;
+
+        while spacing * zoom < MIN_SPACING {
+            spacing *= factor;
+        }
+
+        while spacing / factor * zoom >= MIN_SPACING * factor {
+            spacing /= factor;
+        }
+
+        spacing
+    }
+}
+
+//index range of grid lines visible between two world coordinates
+fn line_range(from: f64, to: f64, spacing: f64) -> std::ops::RangeInclusive<i64> {
+    (from / spacing).floor() as i64..=(to / spacing).ceil() as i64
+}
+
+pub fn render(renderer: &mut Renderer, settings: &GridSettings) {
+    let (width, height) = (renderer.size.0 as i32, renderer.size.1 as i32);
+    let top_left = renderer.camera.screen_to_world(0.0, 0.0);
+    let bottom_right = renderer.camera.screen_to_world(width as f64, height as f64);
+    let spacing = settings.adapted_spacing(renderer.camera.zoom);
+    let major_every = settings.major_every.max(1) as i64;
+
+    if settings.show_grid {
+        for major in [false, true] {
+            if major {
+                renderer.set_color(205, 205, 205, 255);
+            } else {
+                renderer.set_color(235, 235, 235, 255);
+            }
+
+            for i in line_range(top_left.x, bottom_right.x, spacing) {
+                if (i % major_every == 0) == major {
+                    let x = renderer.camera.world_to_screen(Vector2::new(i as f64 * spacing, 0.0)).x as i32;
+                    renderer.line(x, 0, x, height);
+                }
+            }
+
+            for i in line_range(top_left.y, bottom_right.y, spacing) {
+                if (i % major_every == 0) == major {
+                    let y = renderer.camera.world_to_screen(Vector2::new(0.0, i as f64 * spacing)).y as i32;
+                    renderer.line(0, y, width, y);
+                }
+            }
+        }
+
+        let origin = renderer.camera.world_to_screen(Vector2::zero());
+
+        renderer.set_color(120, 120, 120, 255);
+        renderer.line(origin.x as i32, 0, origin.x as i32, height);
+        renderer.line(0, origin.y as i32, width, origin.y as i32);
+    }
+
+    if settings.show_ruler {
+        render_ruler(renderer, top_left, bottom_right, spacing * major_every as f64);
+    }
+}
+
+fn label(value: f64, spacing: f64) -> String {
+    if spacing >= 1.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+//major line coordinates along the top and left edges
+fn render_ruler(renderer: &mut Renderer, top_left: Vector2, bottom_right: Vector2, spacing: f64) {
+    let (width, height) = (renderer.size.0 as i32, renderer.size.1 as i32);
+    let (ruler_size, side_ruler_size, label_height) = (scaled(RULER_SIZE), scaled(SIDE_RULER_SIZE), scaled(LABEL_HEIGHT));
+
+    renderer.set_color(60, 60, 60, 255);
+    renderer.fill_rect(0, 0, width, ruler_size);
+    renderer.fill_rect(0, 0, side_ruler_size, height);
+
+    for i in line_range(top_left.x, bottom_right.x, spacing) {
+        let value = i as f64 * spacing;
+        let x = renderer.camera.world_to_screen(Vector2::new(value, 0.0)).x as i32;
+
+        if x < side_ruler_size {
+            continue;
+        }
+
+        renderer.set_color(200, 200, 200, 255);
+        renderer.line(x, ruler_size - scaled(5), x, ruler_size);
+
+        renderer.draw_text(DEFAULT_FONT, &label(value, spacing), label_height, x + 2, 1);
+    }
+
+    for i in line_range(top_left.y, bottom_right.y, spacing) {
+        let value = i as f64 * spacing;
+        let y = renderer.camera.world_to_screen(Vector2::new(0.0, value)).y as i32;
+
+        if y < ruler_size {
+            continue;
+        }
+
+        renderer.set_color(200, 200, 200, 255);
+        renderer.line(side_ruler_size - scaled(5), y, side_ruler_size, y);
+
+        renderer.draw_text(DEFAULT_FONT, &label(value, spacing), label_height, 2, y + 1);
+    }
+}
@@ -0,0 +1,206 @@
+pub mod grid;
+pub mod overlays;
+pub mod svg;
+pub mod trails;
+
+use std::{collections::HashMap, f64::consts::PI};
+
+use chsl::{math::{matrix::Matrix, vector2::Vector2}, physics::{rigidbody::{Collider, RigidBody}, world::PhysicsWorld}};
+
+use crate::renderer::Renderer;
+use grid::GridSettings;
+use trails::Trails;
+
+pub const STATIC_COLOR: (u8, u8, u8) = (130, 130, 130);
+pub const DYNAMIC_COLOR: (u8, u8, u8) = (70, 130, 220);
+pub const HOVERED_COLOR: (u8, u8, u8) = (240, 170, 40);
+pub const SELECTED_COLOR: (u8, u8, u8) = (220, 50, 50);
+const PLAIN_COLOR: (u8, u8, u8) = (210, 210, 210);
+
+pub struct DebugRenderSettings {
+    pub filled: bool,
+    pub color_by_state: bool,
+    pub hovered_body: Option<String>,
+    pub selected_body: Option<String>,
+    //picked in the view panel, chsl bodies have nowhere to keep these
+    pub body_colors: HashMap<String, (u8, u8, u8)>,
+    pub show_velocity: bool,
+    pub show_angular_velocity: bool,
+    //world units drawn per unit of velocity, radians of arc per rad/s
+    pub vector_scale: f64,
+    pub show_aabb: bool,
+    pub show_axes: bool,
+    //outline and overlay width in pixels
+    pub line_width: f64,
+    pub antialias: bool,
+    pub trails: Trails,
+    pub grid: GridSettings,
+}
+
+impl DebugRenderSettings {
+    pub fn new() -> Self {
+        Self {
+            filled: false,
+            color_by_state: false,
+            hovered_body: None,
+            selected_body: None,
+            body_colors: HashMap::new(),
+            show_velocity: false,
+            show_angular_velocity: false,
+            vector_scale: 0.5,
+            show_aabb: false,
+            show_axes: false,
+            line_width: 1.0,
+            antialias: false,
+            trails: Trails::new(),
+            grid: GridSettings::new(),
+        }
+    }
+}
+
+pub trait DebugRender {
+    fn debug_render(&mut self, renderer: &mut Renderer, settings: &DebugRenderSettings);
+}
+
+//static bodies are the ones nothing can push
+pub fn is_static(body: &RigidBody) -> bool {
+    body.inv_mass == 0.0
+}
+
+//a picked colour wins, then the state colours if they're on
+pub fn fill_color(id: &str, body: &RigidBody, settings: &DebugRenderSettings) -> (u8, u8, u8) {
+    match settings.body_colors.get(id) {
+        Some(color) => *color,
+        None if !settings.color_by_state => PLAIN_COLOR,
+        None if is_static(body) => STATIC_COLOR,
+        None => DYNAMIC_COLOR,
+    }
+}
+
+//local to world, minus the translation
+pub fn body_transform(body: &RigidBody) -> Matrix {
+    Matrix::new().scale(body.scale).rot(body.rotation)
+}
+
+//world space (min, max) corners of the transformed collider
+pub fn body_aabb(body: &RigidBody) -> (Vector2, Vector2) {
+    let transform = body_transform(body);
+
+    match &body.collider {
+        //an ellipse after scaling, its extents come from the transformed axes
+        Collider::Circle { radius } => {
+            let x_axis = transform.vec_mul(&Vector2::new(*radius, 0.0));
+            let y_axis = transform.vec_mul(&Vector2::new(0.0, *radius));
+            let half_width = x_axis.x.hypot(y_axis.x);
+            let half_height = x_axis.y.hypot(y_axis.y);
+
+            (
+                Vector2::new(body.position.x - half_width, body.position.y - half_height),
+                Vector2::new(body.position.x + half_width, body.position.y + half_height),
+            )
+        }
+
+        Collider::Polygon { vertices } => {
+            let mut min = Vector2::new(f64::MAX, f64::MAX);
+            let mut max = Vector2::new(f64::MIN, f64::MIN);
+
+            for vertex in vertices {
+                let point = transform.vec_mul(vertex);
+                min = Vector2::new(min.x.min(point.x), min.y.min(point.y));
+                max = Vector2::new(max.x.max(point.x), max.y.max(point.y));
+            }
+
+            (min + body.position, max + body.position)
+        }
+    }
+}
+
+//collider outline in world space, circles get enough segments to look round at this zoom
+pub fn body_outline(body: &RigidBody, zoom: f64) -> Vec<Vector2> {
+    let mut points = vec![];
+
+    match &body.collider {
+        Collider::Circle { radius } => {
+            let mut angle: f64 = 0.0;
+            let screen_radius = radius * body.scale.x.abs().max(body.scale.y.abs()) * zoom;
+            let iterations = Renderer::circle_segments(screen_radius) as f64;
+
+            for _ in 0..iterations as usize {
+                angle += PI * 2.0 / iterations;
+                points.push(Vector2::new(angle.sin() * radius, angle.cos() * radius))
+            }
+        }
+
+        Collider::Polygon { vertices } => {
+            points.append(&mut vertices.clone()); 
+        }
+    }
+
+    let transform = body_transform(body);
+
+    for point in points.iter_mut() {
+        *point = transform.vec_mul(point) + body.position;
+    }
+
+    points
+}
+
+impl DebugRender for PhysicsWorld {
+    fn debug_render(&mut self, renderer: &mut Renderer, settings: &DebugRenderSettings) {
+        renderer.set_line_width(settings.line_width);
+        renderer.set_antialias(settings.antialias);
+
+        settings.trails.render(renderer);
+
+        for (id, body) in self.all_bodies().iter_mut() {
+            let points = body_outline(body, renderer.camera.zoom);
+
+            if points.is_empty() {
+                continue;
+            }
+
+            let screen_points: Vec<Vector2> = points
+                .iter()
+                .map(|point| renderer.camera.world_to_screen(*point))
+                .collect();
+
+            if settings.filled {
+                let (r, g, b) = fill_color(id, body, settings);
+
+                renderer.set_color(r, g, b, 255);
+                renderer.fill_convex_polygon(&screen_points);
+            }
+
+            let (r, g, b) = if settings.selected_body.as_ref() == Some(id) {
+                SELECTED_COLOR
+            } else if settings.hovered_body.as_ref() == Some(id) {
+                HOVERED_COLOR
+            } else {
+                (0, 0, 0)
+            };
+
+            renderer.set_color(r, g, b, 255);
+            renderer.polyline(&screen_points, true);
+
+            if settings.show_aabb {
+                overlays::aabb(renderer, body);
+            }
+
+            if settings.show_axes {
+                overlays::axes(renderer, body);
+            }
+
+            if settings.show_velocity {
+                overlays::velocity(renderer, body, settings.vector_scale);
+            }
+
+            if settings.show_angular_velocity {
+                overlays::angular_velocity(renderer, body, settings.vector_scale);
+            }
+        }
+
+        //the UI is drawn with plain lines
+        renderer.set_line_width(1.0);
+        renderer.set_antialias(false);
+    }
+}
@@ -0,0 +1,69 @@
+use std::f64::consts::PI;
+
+use chsl::{math::vector2::Vector2, physics::rigidbody::RigidBody};
+
+use crate::renderer::Renderer;
+
+use super::body_aabb;
+
+//in screen pixels so they read the same at any zoom
+const ARROW_HEAD: f64 = 8.0;
+const ARC_RADIUS: f64 = 18.0;
+const AXIS_LENGTH: f64 = 25.0;
+const ORIGIN_SIZE: f64 = 4.0;
+
+pub fn velocity(renderer: &mut Renderer, body: &RigidBody, scale: f64) {
+    let from = renderer.camera.world_to_screen(body.position);
+    let to = renderer.camera.world_to_screen(Vector2::new(
+        body.position.x + body.velocity.x * scale,
+        body.position.y + body.velocity.y * scale,
+    ));
+
+    renderer.set_color(0, 170, 60, 255);
+    renderer.arrow(from, to, ARROW_HEAD);
+}
+
+//sweeps from the body's rotation, one full turn at most
+pub fn angular_velocity(renderer: &mut Renderer, body: &RigidBody, scale: f64) {
+    let sweep = (body.ang_velocity * scale).clamp(-2.0 * PI, 2.0 * PI);
+
+    if sweep.abs() < 0.01 {
+        return;
+    }
+
+    let center = renderer.camera.world_to_screen(body.position);
+    let point = |angle: f64| Vector2::new(center.x + angle.cos() * ARC_RADIUS, center.y + angle.sin() * ARC_RADIUS);
+
+    //the head points along the tangent at the end of the sweep
+    let tip = body.rotation + sweep;
+    let head_sweep = (ARROW_HEAD / ARC_RADIUS).min(sweep.abs()) * sweep.signum();
+
+    renderer.set_color(170, 0, 170, 255);
+    renderer.arc(center, ARC_RADIUS, body.rotation, sweep - head_sweep);
+    renderer.arrow(point(tip - head_sweep), point(tip), ARROW_HEAD);
+}
+
+pub fn aabb(renderer: &mut Renderer, body: &RigidBody) {
+    let (min, max) = body_aabb(body);
+    let min = renderer.camera.world_to_screen(min);
+    let max = renderer.camera.world_to_screen(max);
+
+    renderer.set_color(0, 160, 200, 255);
+    renderer.outline_rect(min.x as i32, min.y as i32, (max.x - min.x) as i32 + 1, (max.y - min.y) as i32 + 1);
+}
+
+//origin cross plus the body's local x (red) and y (green) axes
+pub fn axes(renderer: &mut Renderer, body: &RigidBody) {
+    let origin = renderer.camera.world_to_screen(body.position);
+    let (sin, cos) = body.rotation.sin_cos();
+
+    renderer.set_color(0, 0, 0, 255);
+    renderer.segment(Vector2::new(origin.x - ORIGIN_SIZE, origin.y), Vector2::new(origin.x + ORIGIN_SIZE, origin.y));
+    renderer.segment(Vector2::new(origin.x, origin.y - ORIGIN_SIZE), Vector2::new(origin.x, origin.y + ORIGIN_SIZE));
+
+    renderer.set_color(220, 0, 0, 255);
+    renderer.arrow(origin, Vector2::new(origin.x + cos * AXIS_LENGTH, origin.y + sin * AXIS_LENGTH), ARROW_HEAD);
+
+    renderer.set_color(0, 180, 0, 255);
+    renderer.arrow(origin, Vector2::new(origin.x - sin * AXIS_LENGTH, origin.y + cos * AXIS_LENGTH), ARROW_HEAD);
+}
@@ -0,0 +1,167 @@
+use std::{collections::HashMap, fmt::Write, fs};
+
+use chsl::{math::vector2::Vector2, physics::{constraint::Constraint, rigidbody::{Collider, RigidBody}, world::PhysicsWorld}};
+
+use super::{body_aabb, body_outline, body_transform, fill_color, DebugRenderSettings};
+
+//world units around the bodies so outlines aren't cut by the edge
+const MARGIN: f64 = 20.0;
+const LABEL_SIZE: f64 = 12.0;
+
+//the whole scene in world units, not just what the camera sees
+pub fn export(physics_world: &mut PhysicsWorld, settings: &DebugRenderSettings, path: &str) -> Result<(), String> {
+    fs::write(path, document(physics_world, settings)).map_err(|e| e.to_string())
+}
+
+fn document(physics_world: &mut PhysicsWorld, settings: &DebugRenderSettings) -> String {
+    let mut ids: Vec<String> = physics_world.all_bodies().keys().cloned().collect();
+    ids.sort();
+
+    let mut min = Vector2::new(f64::MAX, f64::MAX);
+    let mut max = Vector2::new(f64::MIN, f64::MIN);
+
+    for body in physics_world.all_bodies().values() {
+        let (body_min, body_max) = body_aabb(body);
+        min = Vector2::new(min.x.min(body_min.x), min.y.min(body_min.y));
+        max = Vector2::new(max.x.max(body_max.x), max.y.max(body_max.y));
+    }
+
+    if ids.is_empty() {
+        min = Vector2::zero();
+        max = Vector2::zero();
+    }
+
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+        min.x - MARGIN,
+        min.y - MARGIN,
+        max.x - min.x + MARGIN * 2.0,
+        max.y - min.y + MARGIN * 2.0,
+    ).unwrap();
+
+    writeln!(out, "<g id=\"bodies\" stroke=\"black\" stroke-width=\"1\">").unwrap();
+
+    for id in &ids {
+        let body = &physics_world.all_bodies()[id];
+
+        let fill = if settings.filled {
+            let (r, g, b) = fill_color(id, body, settings);
+            format!("rgb({},{},{})", r, g, b)
+        } else {
+            "none".to_string()
+        };
+
+        writeln!(out, "{}", shape(body, &fill)).unwrap();
+    }
+
+    writeln!(out, "</g>").unwrap();
+
+    let mut constraint_ids: Vec<String> = physics_world.all_constraints().keys().cloned().collect();
+    constraint_ids.sort();
+
+    let positions: HashMap<String, Vector2> = physics_world
+        .all_bodies()
+        .iter()
+        .map(|(id, body)| (id.clone(), body.position))
+        .collect();
+
+    writeln!(out, "<g id=\"constraints\" stroke=\"red\" stroke-width=\"1\">").unwrap();
+
+    for id in &constraint_ids {
+        let ends = match &physics_world.all_constraints()[id] {
+            //anchored joints pull a body towards a point in the world
+            Constraint::SlideJoint { body, position, .. } | Constraint::FixedJoint { body, position, .. } => {
+                positions.get(body).map(|body| (*position, *body))
+            }
+            //the rest tie two bodies together
+            Constraint::DistanceJoint { body_a, body_b, .. } => positions.get(body_a).zip(positions.get(body_b)).map(|(a, b)| (*a, *b)),
+        };
+
+        if let Some((start, end)) = ends {
+            writeln!(
+                out,
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
+                start.x, start.y, end.x, end.y,
+            ).unwrap();
+        }
+    }
+
+    writeln!(out, "</g>").unwrap();
+
+    writeln!(out, "<g id=\"labels\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\">", LABEL_SIZE).unwrap();
+
+    for id in &ids {
+        let body = &physics_world.all_bodies()[id];
+
+        writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\">{}</text>",
+            body.position.x,
+            body.position.y + LABEL_SIZE / 3.0,
+            escape(id),
+        ).unwrap();
+    }
+
+    writeln!(out, "</g>").unwrap();
+    writeln!(out, "</svg>").unwrap();
+
+    out
+}
+
+fn shape(body: &RigidBody, fill: &str) -> String {
+    match &body.collider {
+        //kept as a real circle so it stays round at any zoom, scale and rotation go in the transform
+        Collider::Circle { radius } => {
+            let transform = body_transform(body);
+            let x_axis = transform.vec_mul(&Vector2::new(1.0, 0.0));
+            let y_axis = transform.vec_mul(&Vector2::new(0.0, 1.0));
+
+            format!(
+                "<circle r=\"{}\" fill=\"{}\" vector-effect=\"non-scaling-stroke\" transform=\"matrix({} {} {} {} {} {})\"/>",
+                radius, fill, x_axis.x, x_axis.y, y_axis.x, y_axis.y, body.position.x, body.position.y,
+            )
+        }
+
+        Collider::Polygon { .. } => {
+            let points: Vec<String> = body_outline(body, 1.0)
+                .iter()
+                .map(|point| format!("{},{}", point.x, point.y))
+                .collect();
+
+            format!("<polygon points=\"{}\" fill=\"{}\"/>", points.join(" "), fill)
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use chsl::physics::bounding_box::BoundingBox;
+
+    use super::*;
+
+    #[test]
+    fn two_body_constraints_are_lines() {
+        let mut physics_world = PhysicsWorld::new(BoundingBox { x: 0.0, y: 0.0, width: 1000.0, height: 1000.0 });
+        physics_world.add_body("a", RigidBody::new_circle(Vector2::new(10.0, 20.0), 0.0, 5.0, false));
+        physics_world.add_body("b", RigidBody::new_circle(Vector2::new(30.0, 40.0), 0.0, 5.0, false));
+        physics_world.add_constraint("rope", Constraint::DistanceJoint {
+            body_a: "a".to_string(),
+            body_b: "b".to_string(),
+            distance: 28.0,
+        });
+
+        let svg = document(&mut physics_world, &DebugRenderSettings::new());
+
+        assert!(svg.contains("<line x1=\"10\" y1=\"20\" x2=\"30\" y2=\"40\"/>"));
+    }
+}
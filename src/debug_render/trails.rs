@@ -0,0 +1,73 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chsl::{math::vector2::Vector2, physics::world::PhysicsWorld};
+
+use crate::renderer::Renderer;
+
+const TRAIL_COLOR: (u8, u8, u8) = (40, 90, 200);
+
+pub struct Trails {
+    history: HashMap<String, VecDeque<Vector2>>,
+    //points kept per body
+    pub length: usize,
+    pub all: bool,
+    pub enabled: HashSet<String>,
+}
+
+impl Trails {
+    pub fn new() -> Self {
+        Self {
+            history: HashMap::new(),
+            length: 120,
+            all: false,
+            enabled: HashSet::new(),
+        }
+    }
+
+    fn tracking(&self, id: &String) -> bool {
+        self.all || self.enabled.contains(id)
+    }
+
+    //only stores a point when the body moved, so pausing doesn't eat the trail
+    pub fn record(&mut self, physics_world: &mut PhysicsWorld) {
+        let bodies = physics_world.all_bodies();
+
+        self.history.retain(|id, _| bodies.contains_key(id));
+        self.enabled.retain(|id| bodies.contains_key(id));
+
+        for (id, body) in bodies.iter() {
+            if !self.tracking(id) {
+                self.history.remove(id);
+                continue;
+            }
+
+            let trail = self.history.entry(id.clone()).or_default();
+
+            let moved = trail.back().is_none_or(|last| last.x != body.position.x || last.y != body.position.y);
+
+            if moved {
+                trail.push_back(body.position);
+            }
+
+            while trail.len() > self.length {
+                trail.pop_front();
+            }
+        }
+    }
+
+    pub fn render(&self, renderer: &mut Renderer) {
+        for trail in self.history.values() {
+            if trail.len() < 2 {
+                continue;
+            }
+
+            for i in 1..trail.len() {
+                //oldest segment is nearly transparent, newest is opaque, so it fades over whatever is behind it
+                let t = i as f64 / (trail.len() - 1) as f64;
+
+                renderer.set_color(TRAIL_COLOR.0, TRAIL_COLOR.1, TRAIL_COLOR.2, (255.0 * t) as u8);
+                renderer.world_line(trail[i - 1], trail[i]);
+            }
+        }
+    }
+}
@@ -67,11 +67,9 @@ impl DebugGuiLayout<Constraint> for ConstraintDebugGui {
             let max = renderer.size.0.max(renderer.size.1) as f64;
 
             renderer.set_color(255, 0, 0, 255);
-            renderer.line(
-                (position.x + rotation.cos() * *rotation) as i32, 
-                (position.y - rotation.sin() * *rotation) as i32, 
-                (position.x - rotation.cos() * *rotation) as i32, 
-                (position.y + rotation.sin() * *rotation) as i32, 
+            renderer.world_line(
+                Vector2::new(position.x + rotation.cos() * *rotation, position.y - rotation.sin() * *rotation), 
+                Vector2::new(position.x - rotation.cos() * *rotation, position.y + rotation.sin() * *rotation), 
             );
         }
 
@@ -1,16 +1,22 @@
 mod rigidbody_gui;
 mod constraint_guis;
+mod simulation_gui;
+mod world_gui;
+mod profiler_gui;
+mod view_gui;
 
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 use chsl::{math::{unique_id::{self, unique_id}, vector2::Vector2}, physics::{bounding_box::BoundingBox, constraint::Constraint, rigidbody::RigidBody, world::PhysicsWorld}};
 use constraint_guis::ConstraintDebugGui;
 use rigidbody_gui::RigidBodyDebugGui;
+use simulation_gui::SimulationDebugGui;
+use world_gui::WorldGui;
+use profiler_gui::ProfilerGui;
+use view_gui::ViewGui;
 use sdl2::{keyboard::Keycode, mouse::MouseButton};
-use serde::{Deserialize, Serialize};
-use rmp_serde::{Deserializer, Serializer};
 
-use crate::{gui_eng::{button::ClickElement, mouse_over_panel, text_input::TextInput, toggle::ToggleGroup, DebugGui, DebugGuiLayout, Panel}, panel, renderer::Renderer, utils::input::Input};
+use crate::{debug_render::{svg, DebugRenderSettings}, gui_eng::{self, button::ClickElement, mouse_over_panel, number_text::NumberTextInput, text_input::TextInput, toggle::ToggleGroup, DebugGui, DebugGuiLayout, Panel}, panel, renderer::Renderer, simulation::Simulation, utils::{frame_recorder::FrameRecorder, input::Input, input_recording::InputRecording, scene, screenshot::Screenshot, TimerUtil}};
 
 pub struct AppUI {
     //UI
@@ -20,6 +26,8 @@ pub struct AppUI {
     move_button: ClickElement,
     view_debug_body: ClickElement,
     static_body: ClickElement,
+    time_scale: NumberTextInput,
+    ui_scale: NumberTextInput,
     hide: ClickElement,
     
     bodies_panel: Panel,
@@ -42,10 +50,37 @@ pub struct AppUI {
     file_panel_path: TextInput,
     file_panel_load_button: ClickElement,
     file_panel_save_button: ClickElement,
+    file_panel_recording_path: TextInput,
+    file_panel_record_input: ClickElement,
+    file_panel_replay_input: ClickElement,
+    file_panel_screenshot: ClickElement,
+    file_panel_screenshot_ui: ClickElement,
+    file_panel_export_svg: ClickElement,
+    file_panel_record_frames: ClickElement,
+    file_panel_record_gif: ClickElement,
+    file_panel_record_every: NumberTextInput,
+    frame_recorder: Option<FrameRecorder>,
+    //both wait for the physics step, see update_input_recording
+    start_input_recording: bool,
+    pending_replay: Option<(PhysicsWorld, Option<scene::Bounds>, InputRecording)>,
+    //taken by the main loop once the world is drawn
+    screenshot: Option<Screenshot>,
+
+    simulation_gui: SimulationDebugGui,
+    simulation_panel_open: ClickElement,
+
+    world_gui: WorldGui,
+    world_panel_open: ClickElement,
+
+    profiler_gui: ProfilerGui,
+    profiler_panel_open: ClickElement,
+
+    view_gui: ViewGui,
+    view_panel_open: ClickElement,
 }
 
 impl AppUI {
-    pub fn new() -> Self { 
+    pub fn new(simulation: &mut Simulation, bounds: Option<scene::Bounds>) -> Self { 
         Self {
             //UI
             rigidbody_panels: HashMap::new(),
@@ -66,6 +101,8 @@ impl AppUI {
             static_body: ClickElement::new_toggle("Static"),
             grab_button: ClickElement::new_button("Grab Bodies"),
             move_button: ClickElement::new_button("Move"),
+            time_scale: NumberTextInput::new(simulation.time_scale(), "Time Scale"),
+            ui_scale: NumberTextInput::new(gui_eng::ui_scale(), "UI Scale"),
             hide: ClickElement::new_button("Hide"),
             bodies_panel: Panel::new(
                 BoundingBox {
@@ -115,6 +152,27 @@ impl AppUI {
             file_panel_path: TextInput::new("saves/", "Path"),
             file_panel_load_button: ClickElement::new_button("Load"),
             file_panel_save_button: ClickElement::new_button("Save"),
+            file_panel_recording_path: TextInput::new("saves/input.rec", "Recording Path"),
+            file_panel_record_input: ClickElement::new_toggle("Record Input"),
+            file_panel_replay_input: ClickElement::new_button("Replay Input"),
+            file_panel_screenshot: ClickElement::new_button("Screenshot"),
+            file_panel_screenshot_ui: ClickElement::new_toggle("Include UI"),
+            file_panel_export_svg: ClickElement::new_button("Export SVG"),
+            file_panel_record_frames: ClickElement::new_toggle("Record Frames"),
+            file_panel_record_gif: ClickElement::new_toggle("As GIF"),
+            file_panel_record_every: NumberTextInput::new(1.0, "Every Nth Frame"),
+            frame_recorder: None,
+            start_input_recording: false,
+            pending_replay: None,
+            screenshot: None,
+            simulation_gui: simulation.debug_gui(Vector2::new(665.0, 50.0), "simulation_panel"),
+            simulation_panel_open: ClickElement::new_toggle("Simulation"),
+            world_gui: WorldGui::new(bounds, simulation),
+            world_panel_open: ClickElement::new_toggle("World"),
+            profiler_gui: ProfilerGui::new(),
+            profiler_panel_open: ClickElement::new_toggle("Profiler"),
+            view_gui: ViewGui::new(),
+            view_panel_open: ClickElement::new_toggle("View"),
         }
     } 
 
@@ -124,9 +182,16 @@ impl AppUI {
         for key in panel_keys {
             if !physics_world.all_bodies().contains_key(&key) {
                 self.rigidbody_panels.remove(&key);
+                self.view_gui.settings.body_colors.remove(&key);
+
+                if self.view_gui.settings.selected_body.as_ref() == Some(&key) {
+                    self.view_gui.settings.selected_body = None;
+                }
             }
         }
 
+        self.view_gui.settings.hovered_body = None;
+
         for (id, body) in physics_world.all_bodies().iter_mut() {
             if !self.rigidbody_panels.contains_key(id) {
                 self.rigidbody_panels.insert(
@@ -140,11 +205,14 @@ impl AppUI {
             panel.render_debug_gui(body, renderer, input, delta_time);
 
             let (x, y) = input.get_mouse_pos();
-            let mouse_pos = Vector2::new(x as f64, y as f64);
+            let mouse_pos = renderer.camera.screen_to_world(x as f64, y as f64);
 
-            if self.view_debug_body.on() {
-                if body.within(mouse_pos) && input.just_pressed_mouse(&MouseButton::Left) {
+            if body.within(mouse_pos) {
+                self.view_gui.settings.hovered_body = Some(id.clone());
+
+                if self.view_debug_body.on() && input.just_pressed_mouse(&MouseButton::Left) {
                     panel.get_panel().hidden = false;
+                    self.view_gui.settings.selected_body = Some(id.clone());
                 }
             }
         }
@@ -230,7 +298,75 @@ impl AppUI {
         }
     }
     
-    pub fn render(&mut self, physics_world: &mut PhysicsWorld, renderer: &mut Renderer, input: &Input, delta_time: f64) { 
+    //clicks and scrolls here belong to a panel, not the scene behind it
+    pub fn mouse_over_ui(&mut self, input: &Input) -> bool {
+        let (x, y) = input.get_mouse_pos();
+
+        mouse_over_panel(vec![&mut self.main_panel, &mut self.bodies_panel, self.simulation_gui.get_panel(), &mut self.world_gui.panel, &mut self.profiler_gui.panel, &mut self.view_gui.panel], x, y).is_some()
+    }
+
+    pub fn debug_render_settings(&self) -> &DebugRenderSettings {
+        &self.view_gui.settings
+    }
+
+    pub fn take_screenshot(&mut self) -> Option<Screenshot> {
+        self.screenshot.take()
+    }
+
+    //a .gif path is encoded as it goes, anything else is a folder of pngs
+    pub fn start_frame_recording(&mut self, path: &str, size: (u32, u32), every: usize) -> Result<(), String> {
+        self.stop_frame_recording();
+        self.frame_recorder = Some(FrameRecorder::new(path, size, every)?);
+        self.file_panel_record_frames.force_on();
+        println!("Recording frames to {}", path);
+        Ok(())
+    }
+
+    pub fn stop_frame_recording(&mut self) {
+        self.file_panel_record_frames.force_off();
+
+        if let Some(recorder) = self.frame_recorder.take() {
+            match recorder.finish() {
+                Ok(frames) => println!("Recorded {} frames", frames),
+                Err(e) => println!("Unable to finish frame recording: {}", e),
+            }
+        }
+    }
+
+    //called by the main loop after the physics step, so the state a recording starts from
+    //is the one its first frame sees, and a replay restores that same point
+    pub fn update_input_recording(&mut self, physics_world: &mut PhysicsWorld, simulation: &mut Simulation, renderer: &mut Renderer, input: &mut Input) {
+        if std::mem::take(&mut self.start_input_recording) {
+            match scene::world_to_bytes(physics_world, self.world_gui.bounds()) {
+                Ok(world) => input.start_recording(InputRecording::new(world, simulation.state(), renderer.camera.clone(), renderer.size, gui_eng::ui_scale())),
+                Err(e) => {
+                    println!("Unable to record input: {}", e);
+                    self.file_panel_record_input.force_off();
+                }
+            }
+        }
+
+        if let Some((world, bounds, recording)) = self.pending_replay.take() {
+            *physics_world = world;
+            simulation.restore(&recording.simulation);
+            renderer.camera = recording.camera.clone();
+            gui_eng::set_ui_scale(recording.ui_scale);
+
+            self.time_scale.set_value(simulation.time_scale());
+            self.simulation_gui.sync(simulation);
+            self.world_gui.sync(simulation);
+            self.world_gui.set_bounds(bounds);
+
+            input.start_playback(recording);
+        }
+    }
+
+    //fed by the main loop, the world is only drawn after the UI
+    pub fn frame_recorder(&mut self) -> Option<&mut FrameRecorder> {
+        self.frame_recorder.as_mut()
+    }
+
+    pub fn render(&mut self, physics_world: &mut PhysicsWorld, simulation: &mut Simulation, renderer: &mut Renderer, input: &mut Input, timer_util: &mut TimerUtil, delta_time: f64) { 
         self.render_rigidbody_panels(physics_world, renderer, input, delta_time);
         self.render_constraint_panels(physics_world, renderer, input, delta_time);
 
@@ -246,9 +382,67 @@ impl AppUI {
             &mut self.view_debug_body,
             &mut self.constraints_panel_toggle,
             &mut self.file_panel_open,
+            &mut self.simulation_panel_open,
+            &mut self.world_panel_open,
+            &mut self.profiler_panel_open,
+            &mut self.view_panel_open,
+            &mut self.time_scale,
+            &mut self.ui_scale,
             &mut self.hide
         );
 
+        let shortcuts = !input.text_focused();
+
+        if shortcuts && input.just_pressed(&Keycode::LEFTBRACKET) {
+            simulation.set_time_scale(simulation.time_scale() / 2.0);
+            self.time_scale.set_value(simulation.time_scale());
+        } else if shortcuts && input.just_pressed(&Keycode::RIGHTBRACKET) {
+            simulation.set_time_scale(simulation.time_scale() * 2.0);
+            self.time_scale.set_value(simulation.time_scale());
+        } else if shortcuts && input.just_pressed(&Keycode::BACKSLASH) {
+            simulation.set_time_scale(1.0);
+            self.time_scale.set_value(simulation.time_scale());
+        } else if self.time_scale.get_value() > 0.0 {
+            simulation.set_time_scale(self.time_scale.get_value());
+        }
+
+        //shows the clamped scale once editing is done
+        if !self.time_scale.focused() && self.time_scale.get_value() != simulation.time_scale() {
+            self.time_scale.set_value(simulation.time_scale());
+        }
+
+        //ctrl +/- step the scale, ctrl 0 resets it
+        let ctrl = input.held(&Keycode::LCTRL) || input.held(&Keycode::RCTRL);
+
+        if ctrl && input.just_pressed(&Keycode::EQUALS) {
+            gui_eng::set_ui_scale(gui_eng::ui_scale() * 1.25);
+        } else if ctrl && input.just_pressed(&Keycode::MINUS) {
+            gui_eng::set_ui_scale(gui_eng::ui_scale() / 1.25);
+        } else if ctrl && input.just_pressed(&Keycode::NUM_0) {
+            gui_eng::set_ui_scale(1.0);
+        } else if self.ui_scale.committed() && self.ui_scale.get_value() > 0.0 {
+            //only once typing is done, every keystroke would lay the panels out again
+            gui_eng::set_ui_scale(self.ui_scale.get_value());
+        }
+
+        //hotkeys, clamping or a new display can all change it, the field shows what's in use
+        if !self.ui_scale.focused() && self.ui_scale.get_value() != gui_eng::ui_scale() {
+            self.ui_scale.set_value(gui_eng::ui_scale());
+        }
+
+        self.simulation_gui.get_panel().hidden = !self.simulation_panel_open.on();
+        self.simulation_gui.render_debug_gui(simulation, renderer, input, delta_time);
+
+        self.world_gui.panel.hidden = !self.world_panel_open.on();
+        self.world_gui.render(physics_world, simulation, renderer, input, delta_time);
+
+        self.profiler_gui.panel.hidden = !self.profiler_panel_open.on();
+        self.profiler_gui.render(timer_util, renderer, input, delta_time);
+
+        self.view_gui.panel.hidden = !self.view_panel_open.on();
+        self.view_gui.render(physics_world, renderer, input, delta_time);
+        self.view_gui.settings.trails.record(physics_world);
+
         self.file_panel.hidden = !self.file_panel_open.on();
         
         panel!(
@@ -258,21 +452,97 @@ impl AppUI {
             delta_time,
             &mut self.file_panel_path,
             &mut self.file_panel_save_button,
-            &mut self.file_panel_load_button
+            &mut self.file_panel_load_button,
+            &mut self.file_panel_recording_path,
+            &mut self.file_panel_record_input,
+            &mut self.file_panel_replay_input,
+            &mut self.file_panel_screenshot,
+            &mut self.file_panel_screenshot_ui,
+            &mut self.file_panel_export_svg,
+            &mut self.file_panel_record_frames,
+            &mut self.file_panel_record_gif,
+            &mut self.file_panel_record_every
         );
 
         if self.file_panel_save_button.just_clicked() {
-            let mut buf = Vec::new();
-            physics_world.serialize(&mut Serializer::new(&mut buf)).unwrap();
-            let path = self.file_panel_path.get_value();
-            fs::write(path, buf).expect("Unable to write to file or it doesn't exist");
+            if let Err(e) = scene::save_world(physics_world, self.world_gui.bounds(), &self.file_panel_path.get_value()) {
+                println!("Unable to save world: {}", e);
+            }
         }
 
         if self.file_panel_load_button.just_clicked() {
-            let path = self.file_panel_path.get_value();
-            let contents = fs::read(path).unwrap();
-            let mut de = Deserializer::new(&contents[..]);
-            *physics_world = PhysicsWorld::deserialize(&mut de).expect("Yeehaa");
+            match scene::load_world(&self.file_panel_path.get_value()) {
+                Ok((world, bounds)) => {
+                    if bounds.is_none() {
+                        println!("No bounds were saved with this world, apply them in the World panel before saving it again");
+                    }
+
+                    *physics_world = world;
+                    simulation.timeline.clear();
+                    self.world_gui.set_bounds(bounds);
+                }
+                Err(e) => println!("Unable to load world: {}", e),
+            }
+        }
+
+        if self.file_panel_record_input.just_clicked() && !input.playing_back() {
+            if self.file_panel_record_input.on() {
+                self.start_input_recording = true;
+            } else if let Some(recording) = input.stop_recording() {
+                if let Err(e) = recording.save(&self.file_panel_recording_path.get_value()) {
+                    println!("Unable to save input recording: {}", e);
+                }
+            }
+        }
+
+        if self.file_panel_replay_input.just_clicked() && !input.recording() {
+            match InputRecording::load(&self.file_panel_recording_path.get_value()) {
+                //resizing the window to match isn't ours to do, the window manager has the last word
+                Ok(recording) if recording.window_size != renderer.size => println!(
+                    "Unable to replay input recording: it was made in a {}x{} window, this one is {}x{}",
+                    recording.window_size.0, recording.window_size.1, renderer.size.0, renderer.size.1,
+                ),
+                Ok(recording) => match scene::world_from_bytes(&recording.world) {
+                    Ok((world, bounds)) => self.pending_replay = Some((world, bounds, recording)),
+                    Err(e) => println!("Unable to load input recording: {}", e),
+                },
+                Err(e) => println!("Unable to load input recording: {}", e),
+            }
+        }
+
+        if self.file_panel_screenshot.just_clicked() || input.just_pressed(&Keycode::F12) {
+            self.screenshot = Some(Screenshot {
+                path: scene::export_path(&self.file_panel_path.get_value(), "screenshot", "png"),
+                include_ui: self.file_panel_screenshot_ui.on(),
+            });
+        }
+
+        if self.file_panel_export_svg.just_clicked() {
+            let path = scene::export_path(&self.file_panel_path.get_value(), "scene", "svg");
+
+            match svg::export(physics_world, &self.view_gui.settings, &path) {
+                Ok(()) => println!("Exported scene to {}", path),
+                Err(e) => println!("Unable to export svg: {}", e),
+            }
+        }
+
+        if self.file_panel_record_frames.just_clicked() {
+            if self.file_panel_record_frames.on() {
+                let save_path = self.file_panel_path.get_value();
+                let path = if self.file_panel_record_gif.on() {
+                    scene::export_path(&save_path, "recording", "gif")
+                } else {
+                    scene::export_path(&save_path, "frames", "")
+                };
+                let every = self.file_panel_record_every.get_value().max(1.0) as usize;
+
+                if let Err(e) = self.start_frame_recording(&path, renderer.size, every) {
+                    println!("Unable to record frames: {}", e);
+                    self.file_panel_record_frames.force_off();
+                }
+            } else {
+                self.stop_frame_recording();
+            }
         }
 
         if self.hide.just_clicked() {
@@ -298,9 +568,7 @@ impl AppUI {
             self.bodies_panel.hidden = true;
         }
         
-        let (x, y) = input.get_mouse_pos();
-
-        if let None = mouse_over_panel(vec![&mut self.main_panel, &mut self.bodies_panel], x, y) {
+        if !self.mouse_over_ui(input) {
             if input.just_pressed_mouse(&MouseButton::Left) {
                 let (x, y) = input.get_mouse_pos();
                 
@@ -309,7 +577,7 @@ impl AppUI {
                     new_id = new_id + " Copy";
                 }
 
-                let new_position = Vector2::new(x as f64, y as f64);
+                let new_position = renderer.camera.screen_to_world(x as f64, y as f64);
 
                 match self.place_body_options.active_toggle().as_deref() {
                     Some("Circle") => {
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use chsl::physics::bounding_box::BoundingBox;
+use crate::{gui_eng::{button::ClickElement, graph::Graph, text::Text, text_input::TextInput, Panel}, renderer::Renderer, utils::{input::Input, TimerUtil}};
+
+struct ScopeGui {
+    name: Text,
+    stats: Text,
+    percentiles: Text,
+    graph: Graph,
+}
+
+pub struct ProfilerGui {
+    pub panel: Panel,
+    log: ClickElement,
+    trace_path: TextInput,
+    record_trace: ClickElement,
+    scopes: HashMap<String, ScopeGui>,
+}
+
+impl ProfilerGui {
+    pub fn new() -> Self {
+        let mut result = Self {
+            panel: Panel::new(
+                BoundingBox {
+                    x: 960.0,
+                    y: 500.0,
+                    width: 270.0,
+                    height: 0.0
+                },
+                "profiler_panel",
+            ),
+            log: ClickElement::new_toggle("Log to Stdout"),
+            trace_path: TextInput::new("saves/trace.json", "Trace Path"),
+            record_trace: ClickElement::new_toggle("Record Trace"),
+            scopes: HashMap::new(),
+        };
+        result.panel.hidden = true;
+        result
+    }
+
+    pub fn render(&mut self, timer_util: &mut TimerUtil, renderer: &mut Renderer, input: &Input, delta_time: f64) {
+        self.panel.render(renderer, input);
+        self.panel.drag_start(input);
+        self.panel.drag_end(input);
+
+        if timer_util.log {
+            self.log.force_on();
+        } else {
+            self.log.force_off();
+        }
+
+        if timer_util.tracing() {
+            self.record_trace.force_on();
+        } else {
+            self.record_trace.force_off();
+        }
+
+        self.panel.display(renderer, input, delta_time, &mut self.log);
+        self.panel.display(renderer, input, delta_time, &mut self.trace_path);
+        self.panel.display(renderer, input, delta_time, &mut self.record_trace);
+
+        if self.log.just_clicked() {
+            timer_util.log = self.log.on();
+        }
+
+        if self.record_trace.just_clicked() {
+            if self.record_trace.on() {
+                timer_util.start_trace();
+            } else if let Err(e) = timer_util.stop_trace(&self.trace_path.get_value()) {
+                println!("Unable to write trace: {}", e);
+            }
+        }
+
+        for path in timer_util.scopes() {
+            let Some(stats) = timer_util.stats(path) else { continue };
+
+            let scope = self.scopes.entry(path.clone()).or_insert_with(|| ScopeGui {
+                name: Text::new(1, &(path.clone() + " (ms)")),
+                stats: Text::new(0, ""),
+                percentiles: Text::new(0, ""),
+                graph: Graph::new(30),
+            });
+
+            scope.stats.content = format!("min {:.2} avg {:.2} max {:.2}", stats.min, stats.avg, stats.max);
+            scope.percentiles.content = format!("p50 {:.2} p95 {:.2} p99 {:.2}", stats.p50, stats.p95, stats.p99);
+            scope.graph.values = timer_util.history(path).unwrap().iter().copied().collect();
+
+            self.panel.display(renderer, input, delta_time, &mut scope.name);
+            self.panel.display(renderer, input, delta_time, &mut scope.stats);
+            self.panel.display(renderer, input, delta_time, &mut scope.percentiles);
+            self.panel.display(renderer, input, delta_time, &mut scope.graph);
+        }
+    }
+}
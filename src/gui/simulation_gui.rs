@@ -0,0 +1,130 @@
+use chsl::{math::vector2::Vector2, physics::bounding_box::BoundingBox};
+use sdl2::keyboard::Keycode;
+use crate::{gui_eng::{button::ClickElement, number_text::NumberTextInput, slider::Slider, text::Text, DebugGui, DebugGuiLayout, Panel}, panel, renderer::Renderer, simulation::Simulation, utils::input::Input};
+
+pub struct SimulationDebugGui {
+    panel: Panel,
+    status: Text,
+    pause: ClickElement,
+    step: ClickElement,
+    step_count: NumberTextInput,
+    step_size: NumberTextInput,
+    timeline_text: Text,
+    scrubber: Slider,
+}
+
+impl SimulationDebugGui {
+    //after the simulation was changed from somewhere else, otherwise the inputs write their old values back
+    pub fn sync(&mut self, simulation: &Simulation) {
+        self.step_size.set_value(simulation.step_size);
+    }
+}
+
+impl DebugGuiLayout<Simulation> for SimulationDebugGui {
+    fn get_panel(&mut self) -> &mut Panel {
+        &mut self.panel
+    }
+    fn render_debug_gui(&mut self, simulation: &mut Simulation, renderer: &mut Renderer, input: &Input, delta_time: f64) {
+        //shortcuts work with the panel closed too, but not while typing into a field
+        let shortcuts = !input.text_focused();
+
+        if shortcuts && input.just_pressed(&Keycode::SPACE) {
+            simulation.toggle_pause();
+        }
+
+        if shortcuts && input.just_pressed(&Keycode::PERIOD) {
+            simulation.paused = true;
+            simulation.step(1);
+        }
+
+        if simulation.paused {
+            self.pause.force_on();
+        } else {
+            self.pause.force_off();
+        }
+
+        self.status.content = format!(
+            "Step {} {}",
+            simulation.steps_taken(),
+            if simulation.paused {
+                "(paused)"
+            } else if simulation.falling_behind() {
+                "(behind)"
+            } else {
+                ""
+            }
+        );
+
+        let snapshots = simulation.timeline.len();
+        let current = simulation.timeline.index_of(simulation.steps_taken());
+
+        if let Some(index) = current {
+            self.scrubber.set_value(index as f64 / (snapshots.max(2) - 1) as f64);
+        }
+
+        self.timeline_text.content = format!(
+            "Timeline {}/{}",
+            current.map_or(0, |index| index + 1),
+            snapshots
+        );
+
+        panel!(
+            self.panel,
+            renderer,
+            input,
+            delta_time,
+            &mut self.status,
+            &mut self.pause,
+            &mut self.step,
+            &mut self.step_count,
+            &mut self.step_size,
+            &mut self.timeline_text,
+            &mut self.scrubber
+        );
+
+        if self.scrubber.changed() && snapshots > 0 {
+            let index = (self.scrubber.get_value() * (snapshots - 1) as f64).round() as usize;
+            simulation.rewind(index);
+        }
+
+        if self.pause.just_clicked() {
+            simulation.paused = self.pause.on();
+        }
+
+        if self.step.just_clicked() {
+            simulation.paused = true;
+            simulation.step(self.step_count.get_value().max(1.0) as usize);
+        }
+
+        if self.step_size.get_value() > 0.0 {
+            simulation.step_size = self.step_size.get_value();
+        }
+    }
+}
+
+impl DebugGui for Simulation {
+    type Layout = SimulationDebugGui;
+
+    fn debug_gui(&mut self, position: Vector2, name: &str) -> Self::Layout {
+        let mut result = Self::Layout {
+            panel: Panel::new(
+                BoundingBox {
+                    x: position.x,
+                    y: position.y,
+                    width: 200.0,
+                    height: 0.0
+                },
+                name
+            ),
+            status: Text::new(0, ""),
+            pause: ClickElement::new_toggle("Pause"),
+            step: ClickElement::new_button("Step"),
+            step_count: NumberTextInput::new(1.0, "Steps"),
+            step_size: NumberTextInput::new(self.step_size, "Step Size"),
+            timeline_text: Text::new(0, ""),
+            scrubber: Slider::new(1.0),
+        };
+        result.panel.hidden = true;
+        result
+    }
+}
@@ -0,0 +1,208 @@
+use chsl::physics::{bounding_box::BoundingBox, world::PhysicsWorld};
+use sdl2::keyboard::Keycode;
+use crate::{debug_render::{DebugRenderSettings, DYNAMIC_COLOR, HOVERED_COLOR, SELECTED_COLOR, STATIC_COLOR}, gui_eng::{button::ClickElement, color_label::ColorLabel, number_text::NumberTextInput, text::Text, Panel}, panel, renderer::Renderer, utils::input::Input};
+
+pub struct ViewGui {
+    pub panel: Panel,
+    zoom_text: Text,
+    reset: ClickElement,
+    follow: ClickElement,
+    pub settings: DebugRenderSettings,
+    filled: ClickElement,
+    color_by_state: ClickElement,
+    legend: [ColorLabel; 4],
+    show_velocity: ClickElement,
+    show_angular_velocity: ClickElement,
+    vector_scale: NumberTextInput,
+    show_aabb: ClickElement,
+    show_axes: ClickElement,
+    antialias: ClickElement,
+    line_width: NumberTextInput,
+    all_trails: ClickElement,
+    trail_length: NumberTextInput,
+    show_grid: ClickElement,
+    show_ruler: ClickElement,
+    grid_spacing: NumberTextInput,
+    grid_major_every: NumberTextInput,
+    selected_text: Text,
+    color_r: NumberTextInput,
+    color_g: NumberTextInput,
+    color_b: NumberTextInput,
+    selected_trail: ClickElement,
+    set_color: ClickElement,
+    clear_color: ClickElement,
+}
+
+impl ViewGui {
+    pub fn new() -> Self {
+        let mut show_grid = ClickElement::new_toggle("Grid");
+        show_grid.force_on();
+        let mut show_ruler = ClickElement::new_toggle("Ruler");
+        show_ruler.force_on();
+
+        let mut result = Self {
+            panel: Panel::new(
+                BoundingBox {
+                    x: 255.0,
+                    y: 200.0,
+                    width: 200.0,
+                    height: 0.0
+                },
+                "view_panel",
+            ),
+            zoom_text: Text::new(0, ""),
+            reset: ClickElement::new_button("Reset View"),
+            follow: ClickElement::new_toggle("Follow Selected"),
+            settings: DebugRenderSettings::new(),
+            filled: ClickElement::new_toggle("Filled Bodies"),
+            color_by_state: ClickElement::new_toggle("Color By State"),
+            legend: [
+                ColorLabel::new(STATIC_COLOR, "Static"),
+                ColorLabel::new(DYNAMIC_COLOR, "Dynamic"),
+                ColorLabel::new(HOVERED_COLOR, "Hovered"),
+                ColorLabel::new(SELECTED_COLOR, "Selected"),
+            ],
+            show_velocity: ClickElement::new_toggle("Velocity"),
+            show_angular_velocity: ClickElement::new_toggle("Angular Velocity"),
+            vector_scale: NumberTextInput::new(0.5, "Vector Scale"),
+            show_aabb: ClickElement::new_toggle("Bounding Boxes"),
+            show_axes: ClickElement::new_toggle("Origin & Axes"),
+            antialias: ClickElement::new_toggle("Antialiasing"),
+            line_width: NumberTextInput::new(1.0, "Line Width"),
+            all_trails: ClickElement::new_toggle("All Trails"),
+            trail_length: NumberTextInput::new(120.0, "Trail Length"),
+            show_grid,
+            show_ruler,
+            grid_spacing: NumberTextInput::new(50.0, "Grid Spacing"),
+            grid_major_every: NumberTextInput::new(5.0, "Major Every"),
+            selected_trail: ClickElement::new_toggle("Trail"),
+            selected_text: Text::new(0, ""),
+            color_r: NumberTextInput::new(255.0, "Red"),
+            color_g: NumberTextInput::new(255.0, "Green"),
+            color_b: NumberTextInput::new(255.0, "Blue"),
+            set_color: ClickElement::new_button("Set Body Color"),
+            clear_color: ClickElement::new_button("Clear Body Color"),
+        };
+        result.panel.hidden = true;
+        result
+    }
+
+    pub fn render(&mut self, physics_world: &mut PhysicsWorld, renderer: &mut Renderer, input: &Input, delta_time: f64) {
+        self.zoom_text.content = format!("Zoom {:.2}x", renderer.camera.zoom);
+        //follows whichever body is selected
+        if self.settings.selected_body.as_ref().is_some_and(|id| self.settings.trails.enabled.contains(id)) {
+            self.selected_trail.force_on();
+        } else {
+            self.selected_trail.force_off();
+        }
+
+        self.selected_text.content = match &self.settings.selected_body {
+            Some(id) => format!("Selected: {}", id),
+            None => "Nothing selected".to_string(),
+        };
+
+        panel!(
+            self.panel,
+            renderer,
+            input,
+            delta_time,
+            &mut self.zoom_text,
+            &mut self.reset,
+            &mut self.follow,
+            &mut self.filled,
+            &mut self.color_by_state
+        );
+
+        if self.color_by_state.on() {
+            for label in self.legend.iter_mut() {
+                self.panel.display(renderer, input, delta_time, label);
+            }
+        }
+
+        self.panel
+            .display(renderer, input, delta_time, &mut self.show_velocity)
+            .display(renderer, input, delta_time, &mut self.show_angular_velocity)
+            .display(renderer, input, delta_time, &mut self.vector_scale)
+            .display(renderer, input, delta_time, &mut self.show_aabb)
+            .display(renderer, input, delta_time, &mut self.show_axes)
+            .display(renderer, input, delta_time, &mut self.antialias)
+            .display(renderer, input, delta_time, &mut self.line_width)
+            .display(renderer, input, delta_time, &mut self.all_trails)
+            .display(renderer, input, delta_time, &mut self.trail_length)
+            .display(renderer, input, delta_time, &mut self.show_grid)
+            .display(renderer, input, delta_time, &mut self.show_ruler)
+            .display(renderer, input, delta_time, &mut self.grid_spacing)
+            .display(renderer, input, delta_time, &mut self.grid_major_every)
+            .display(renderer, input, delta_time, &mut self.selected_text)
+            .display(renderer, input, delta_time, &mut self.selected_trail)
+            .display(renderer, input, delta_time, &mut self.color_r)
+            .display(renderer, input, delta_time, &mut self.color_g)
+            .display(renderer, input, delta_time, &mut self.color_b)
+            .display(renderer, input, delta_time, &mut self.set_color)
+            .display(renderer, input, delta_time, &mut self.clear_color);
+
+        self.settings.filled = self.filled.on();
+        self.settings.color_by_state = self.color_by_state.on();
+        self.settings.show_velocity = self.show_velocity.on();
+        self.settings.show_angular_velocity = self.show_angular_velocity.on();
+        self.settings.show_aabb = self.show_aabb.on();
+        self.settings.show_axes = self.show_axes.on();
+        self.settings.antialias = self.antialias.on();
+        self.settings.trails.all = self.all_trails.on();
+
+        self.settings.grid.show_grid = self.show_grid.on();
+        self.settings.grid.show_ruler = self.show_ruler.on();
+
+        if self.grid_spacing.get_value() > 0.0 {
+            self.settings.grid.spacing = self.grid_spacing.get_value();
+        }
+
+        if self.grid_major_every.get_value() >= 2.0 {
+            self.settings.grid.major_every = self.grid_major_every.get_value() as usize;
+        }
+
+        if self.trail_length.get_value() >= 2.0 {
+            self.settings.trails.length = self.trail_length.get_value() as usize;
+        }
+
+        if self.line_width.get_value() > 0.0 {
+            self.settings.line_width = self.line_width.get_value();
+        }
+
+        if self.vector_scale.get_value() > 0.0 {
+            self.settings.vector_scale = self.vector_scale.get_value();
+        }
+
+        if let Some(id) = &self.settings.selected_body {
+            if self.selected_trail.just_clicked() {
+                if self.selected_trail.on() {
+                    self.settings.trails.enabled.insert(id.clone());
+                } else {
+                    self.settings.trails.enabled.remove(id);
+                }
+            }
+
+            if self.set_color.just_clicked() {
+                let channel = |input: &NumberTextInput| input.get_value().clamp(0.0, 255.0) as u8;
+                let color = (channel(&self.color_r), channel(&self.color_g), channel(&self.color_b));
+                self.settings.body_colors.insert(id.clone(), color);
+            }
+
+            if self.clear_color.just_clicked() {
+                self.settings.body_colors.remove(id);
+            }
+        }
+
+        if self.reset.just_clicked() || (input.just_pressed(&Keycode::HOME) && !input.text_focused()) {
+            renderer.camera.reset();
+            self.follow.force_off();
+        }
+
+        if self.follow.on() {
+            if let Some(body) = self.settings.selected_body.as_ref().and_then(|id| physics_world.all_bodies().get(id)) {
+                let size = renderer.size;
+                renderer.camera.center_on(body.position, size);
+            }
+        }
+    }
+}
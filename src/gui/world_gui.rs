@@ -0,0 +1,125 @@
+use chsl::{math::vector2::Vector2, physics::{bounding_box::BoundingBox, world::PhysicsWorld}};
+use crate::{gui_eng::{button::ClickElement, number_text::NumberTextInput, text::Text, Panel}, panel, renderer::Renderer, simulation::{self, Simulation}, utils::{input::Input, scene}};
+
+pub struct WorldGui {
+    pub panel: Panel,
+    //none for saves from before the bounds were kept in them
+    bounds: Option<scene::Bounds>,
+    bounds_text: Text,
+    bounds_x: NumberTextInput,
+    bounds_y: NumberTextInput,
+    bounds_width: NumberTextInput,
+    bounds_height: NumberTextInput,
+    apply_bounds: ClickElement,
+    show_bounds: ClickElement,
+    solver_text: Text,
+    iterations: NumberTextInput,
+    substeps: NumberTextInput,
+}
+
+impl WorldGui {
+    pub fn new(bounds: Option<scene::Bounds>, simulation: &Simulation) -> Self {
+        let mut show_bounds = ClickElement::new_toggle("Show Bounds");
+        show_bounds.force_on();
+
+        let mut result = Self {
+            panel: Panel::new(
+                BoundingBox {
+                    x: 870.0,
+                    y: 50.0,
+                    width: 200.0,
+                    height: 0.0
+                },
+                "world_panel",
+            ),
+            bounds: None,
+            bounds_text: Text::new(1, "Bounds"),
+            bounds_x: NumberTextInput::new(0.0, "X"),
+            bounds_y: NumberTextInput::new(0.0, "Y"),
+            bounds_width: NumberTextInput::new(0.0, "Width"),
+            bounds_height: NumberTextInput::new(0.0, "Height"),
+            apply_bounds: ClickElement::new_button("Apply Bounds"),
+            show_bounds,
+            solver_text: Text::new(1, "Solver"),
+            iterations: NumberTextInput::new(simulation.iterations as f64, "Iterations"),
+            substeps: NumberTextInput::new(simulation.substeps as f64, "Substeps"),
+        };
+        result.panel.hidden = true;
+        result.set_bounds(bounds);
+        result
+    }
+
+    //whenever the world is swapped for another one, it brings its own bounds
+    pub fn set_bounds(&mut self, bounds: Option<scene::Bounds>) {
+        self.bounds = bounds;
+
+        let (x, y, width, height) = self.bounds.unwrap_or_default();
+        self.bounds_x.set_value(x);
+        self.bounds_y.set_value(y);
+        self.bounds_width.set_value(width);
+        self.bounds_height.set_value(height);
+    }
+
+    pub fn bounds(&self) -> Option<scene::Bounds> {
+        self.bounds
+    }
+
+    pub fn sync(&mut self, simulation: &Simulation) {
+        self.iterations.set_value(simulation.iterations as f64);
+        self.substeps.set_value(simulation.substeps as f64);
+    }
+
+    pub fn render(&mut self, physics_world: &mut PhysicsWorld, simulation: &mut Simulation, renderer: &mut Renderer, input: &Input, delta_time: f64) {
+        if let Some((x, y, width, height)) = self.bounds.filter(|_| self.show_bounds.on()) {
+            let top_left = renderer.camera.world_to_screen(Vector2::new(x, y));
+            let bottom_right = renderer.camera.world_to_screen(Vector2::new(x + width, y + height));
+
+            renderer.set_color(0, 200, 0, 255);
+            renderer.outline_rect(
+                top_left.x as i32,
+                top_left.y as i32,
+                (bottom_right.x - top_left.x) as i32,
+                (bottom_right.y - top_left.y) as i32,
+            );
+        }
+
+        panel!(
+            self.panel,
+            renderer,
+            input,
+            delta_time,
+            &mut self.bounds_text,
+            &mut self.bounds_x,
+            &mut self.bounds_y,
+            &mut self.bounds_width,
+            &mut self.bounds_height,
+            &mut self.apply_bounds,
+            &mut self.show_bounds,
+            &mut self.solver_text,
+            &mut self.iterations,
+            &mut self.substeps
+        );
+
+        if self.apply_bounds.just_clicked() {
+            let width = self.bounds_width.get_value();
+            let height = self.bounds_height.get_value();
+
+            if width > 0.0 && height > 0.0 {
+                let (x, y) = (self.bounds_x.get_value(), self.bounds_y.get_value());
+                self.bounds = Some((x, y, width, height));
+
+                simulation::rebuild_with_bounds(physics_world, BoundingBox { x, y, width, height });
+                //snapshots from before would rewind to the old bounds
+                simulation.timeline.clear();
+            }
+        }
+
+        if self.iterations.get_value() >= 1.0 {
+            simulation.iterations = self.iterations.get_value() as usize;
+        }
+
+        if self.substeps.get_value() >= 1.0 {
+            simulation.substeps = self.substeps.get_value() as usize;
+        }
+    }
+}
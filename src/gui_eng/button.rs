@@ -1,8 +1,8 @@
 use sdl2::mouse::MouseButton;
-use crate::renderer::Renderer;
+use crate::renderer::{font_manager::DEFAULT_FONT, Renderer};
 
 use crate::utils::input::Input;
-use super::{Element, Panel, PADDING};
+use super::{padding, scaled, Element, Panel};
 
 pub enum ButtonOrToggle {
     Button,
@@ -21,9 +21,9 @@ impl Element for ClickElement {
         self.just_clicked = false;
 
         let bounds = (
-            panel.bounds.x as i32 + PADDING as i32 / 2,
+            panel.bounds.x as i32 + padding() / 2,
             panel.bounds.y as i32 + panel.y_offset as i32,
-            panel.bounds.width as i32 - PADDING as i32,
+            panel.bounds.width as i32 - padding(),
             self.bounds().0,
         );
  
@@ -49,27 +49,23 @@ impl Element for ClickElement {
             renderer.set_color(120, 120, 120, 255);
         }
         
-        let text_height = bounds.3 - PADDING as i32 / 2;
+        let text_height = bounds.3 - padding() / 2;
         
         renderer.fill_rect(bounds.0, bounds.1, bounds.2, bounds.3);
 
-        let text_texture = unsafe {
-            renderer.get_text_texture("open_sans", &self.text, text_height)
-        };
+        let text_size = renderer.text_size(DEFAULT_FONT, &self.text, text_height);
 
-        renderer.render_text_texture(
-            &text_texture, 
-            bounds.0 + (bounds.2 / 2 - text_texture.size.0 as i32 / 2) as i32, 
-            bounds.1 + PADDING as i32 / 4,
+        renderer.draw_text(
+            DEFAULT_FONT,
+            &self.text,
+            text_height,
+            bounds.0 + bounds.2 / 2 - text_size.0 as i32 / 2,
+            bounds.1 + padding() / 4,
         );
-        
-        unsafe {
-            renderer.free_last_texture();
-        }
     }
 
     fn bounds(&mut self) -> (i32, i32) {
-        (30, i32::MAX)
+        (scaled(30), i32::MAX)
     }
 }
 
@@ -82,6 +78,10 @@ impl ClickElement {
         self.state_or_held = false
     }
 
+    pub fn force_on(&mut self) {
+        self.state_or_held = true
+    }
+
     pub fn on(&self) -> bool {
         self.state_or_held
     }
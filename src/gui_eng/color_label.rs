@@ -0,0 +1,36 @@
+use crate::{renderer::Renderer, utils::input::Input};
+
+use super::{padding, scaled, text::Text, Element, Panel};
+
+//colour swatch followed by a label, for legends
+pub struct ColorLabel {
+    pub color: (u8, u8, u8),
+    text: Text,
+}
+
+impl ColorLabel {
+    pub fn new(color: (u8, u8, u8), content: &str) -> Self {
+        Self {
+            color,
+            text: Text::new(0, content),
+        }
+    }
+}
+
+impl Element for ColorLabel {
+    fn render(&mut self, renderer: &mut Renderer, _input: &Input, panel: &mut Panel, _delta_time: f64) {
+        let size = self.bounds().0;
+        let x = panel.bounds.x as i32 + padding() / 2;
+        let y = panel.bounds.y as i32 + panel.y_offset;
+
+        renderer.set_color(self.color.0, self.color.1, self.color.2, 255);
+        renderer.fill_rect(x, y, size, size);
+
+        //label goes past the swatch
+        self.text.render_offset(renderer, panel, size + padding() / 2);
+    }
+
+    fn bounds(&mut self) -> (i32, i32) {
+        (self.text.bounds().0.max(scaled(12)), i32::MAX)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{renderer::Renderer, utils::input::Input};
+
+use super::{padding, scaled, Element, Panel};
+
+//rolling bar graph, scaled so the tallest value fills it
+pub struct Graph {
+    pub values: Vec<f64>,
+    //at a ui scale of 1
+    height: i32,
+}
+
+impl Graph {
+    pub fn new(height: i32) -> Self {
+        Self {
+            values: vec![],
+            height,
+        }
+    }
+}
+
+impl Element for Graph {
+    fn render(&mut self, renderer: &mut Renderer, _input: &Input, panel: &mut Panel, _delta_time: f64) {
+        let x = panel.bounds.x as i32 + padding() / 2;
+        let y = panel.bounds.y as i32 + panel.y_offset;
+        let width = panel.bounds.width as i32 - padding();
+        let height = scaled(self.height);
+
+        renderer.set_color(70, 70, 70, 255);
+        renderer.fill_rect(x, y, width, height);
+
+        let max = self.values.iter().copied().fold(0.0, f64::max);
+
+        if self.values.is_empty() || max <= 0.0 {
+            return;
+        }
+
+        renderer.set_color(200, 200, 200, 255);
+
+        for (i, value) in self.values.iter().enumerate() {
+            let bar_x = x + (i as i32 * width) / self.values.len() as i32;
+            let bar_height = (value / max * height as f64) as i32;
+
+            renderer.line(bar_x, y + height, bar_x, y + height - bar_height);
+        }
+    }
+
+    fn bounds(&mut self) -> (i32, i32) {
+        (scaled(self.height), i32::MAX)
+    }
+}
@@ -1,17 +1,44 @@
 pub mod button;
+pub mod color_label;
+pub mod graph;
 pub mod text;
 pub mod text_input;
 pub mod toggle;
 pub mod number_text;
+pub mod slider;
 
 use core::f64;
-use std::u32;
+use std::cell::Cell;
 
 use chsl::{math::vector2::Vector2, physics::bounding_box::BoundingBox};
 use crate::{renderer::Renderer, utils::input::Input};
 use sdl2::mouse::MouseButton;
 
+//every metric in here is written for a ui scale of 1
 const PADDING: u32 = 20;
+pub const MIN_UI_SCALE: f64 = 0.5;
+pub const MAX_UI_SCALE: f64 = 4.0;
+
+thread_local! {
+    static UI_SCALE: Cell<f64> = const { Cell::new(1.0) };
+}
+
+pub fn ui_scale() -> f64 {
+    UI_SCALE.with(|scale| scale.get())
+}
+
+pub fn set_ui_scale(scale: f64) {
+    UI_SCALE.with(|current| current.set(scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)));
+}
+
+//sizes given at a scale of 1, for anything drawn in screen space alongside the panels
+pub fn scaled(value: i32) -> i32 {
+    (value as f64 * ui_scale()).round() as i32
+}
+
+fn padding() -> i32 {
+    scaled(PADDING as i32)
+}
 
 pub trait Element {
     fn render(&mut self, renderer: &mut Renderer, input: &Input, panel: &mut Panel, delta_time: f64);
@@ -24,6 +51,8 @@ pub struct Panel {
     y_offset: i32,
     drag_origin: Option<Vector2>,
     pub hidden: bool,
+    //ui scale the bounds are currently laid out at
+    scale: f64,
 }
 
 #[macro_export]
@@ -56,7 +85,7 @@ impl Panel {
     pub fn display<T: Element>(&mut self, renderer: &mut Renderer, input: &Input, delta_time: f64, element: &mut T) -> &mut Self {
         if self.hidden { return self };
         element.render(renderer, input, self, delta_time);
-        self.y_offset += element.bounds().0 + PADDING as i32 / 2;
+        self.y_offset += element.bounds().0 + padding() / 2;
         if self.y_offset as f64 > self.bounds.height {
             self.bounds.height = self.y_offset as f64; 
         }
@@ -65,7 +94,21 @@ impl Panel {
 
     pub fn render(&mut self, renderer: &mut Renderer, input: &Input) {
         if self.hidden { return };
-        self.y_offset = PADDING as i32 / 2;
+        self.y_offset = padding() / 2;
+
+        //keeps panels where they were relative to each other
+        if self.scale != ui_scale() {
+            let ratio = ui_scale() / self.scale;
+            self.bounds.x *= ratio;
+            self.bounds.y *= ratio;
+            self.bounds.width *= ratio;
+            self.bounds.height *= ratio;
+            self.scale = ui_scale();
+        }
+
+        if self.drag_origin.is_none() {
+            self.clamp_to(renderer.size);
+        }
         
         let offset = if let Some(origin) = self.drag_origin {
             let (x, y) = input.get_mouse_pos();
@@ -84,8 +127,15 @@ impl Panel {
         );
     }
 
+    //pulls the panel back on screen, e.g. after the window shrinks or it's dropped past an edge
+    pub fn clamp_to(&mut self, size: (u32, u32)) {
+        self.bounds.x = self.bounds.x.min(size.0 as f64 - self.bounds.width).max(0.0);
+        self.bounds.y = self.bounds.y.min(size.1 as f64 - self.bounds.height).max(0.0);
+    }
+
+    //bounds are at a ui scale of 1, the first render scales them
     pub fn new(bounds: BoundingBox, name: &str) -> Panel {
-        Panel { bounds, hidden: true, drag_origin: None, y_offset: PADDING as i32 / 2, name: name.to_string() }
+        Panel { bounds, hidden: true, drag_origin: None, y_offset: padding() / 2, name: name.to_string(), scale: 1.0 }
     }
 
     pub fn drag_start(&mut self, input: &Input) {
@@ -115,7 +165,7 @@ impl Panel {
 
 pub fn mouse_over_panel(panels: Vec<&mut Panel>, x: i32, y: i32) -> Option<String> {
     for panel in panels.iter() {
-        if panel.bounds.point_within(Vector2::new(x as f64, y as f64)) {
+        if !panel.hidden && panel.bounds.point_within(Vector2::new(x as f64, y as f64)) {
             return Some(panel.name.clone()); 
         }
     }
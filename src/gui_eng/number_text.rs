@@ -1,5 +1,5 @@
 use std::f64;
-use crate::utils::input::Input;
+use crate::{renderer::font_manager::NUMERIC_FONT, utils::input::Input};
 use super::{text_input::TextInput, Element, Panel};
 
 pub struct NumberTextInput {
@@ -9,12 +9,19 @@ pub struct NumberTextInput {
 
 impl NumberTextInput {
     pub fn new(value: f64, placeholder: &str) -> Self {
-        Self { text: TextInput::new(value.to_string().as_str(), placeholder), current_value: value}
+        Self { text: TextInput::new(value.to_string().as_str(), placeholder).with_font(NUMERIC_FONT), current_value: value}
     }    
     pub fn get_value(&self) -> f64 {
         self.current_value
     }
+    pub fn focused(&self) -> bool {
+        self.text.focused()
+    }
+    pub fn committed(&self) -> bool {
+        self.text.committed()
+    }
     pub fn set_value(&mut self, num: f64) {
+        self.current_value = num;
         self.text.set_value(num.to_string());
     } 
 }
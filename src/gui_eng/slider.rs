@@ -0,0 +1,84 @@
+use sdl2::{mouse::MouseButton, rect::Rect};
+
+use crate::{renderer::Renderer, utils::input::Input};
+
+use super::{padding, scaled, Element, Panel};
+
+pub struct Slider {
+    value: f64,
+    dragging: bool,
+    changed: bool,
+}
+
+impl Slider {
+    pub fn new(value: f64) -> Self {
+        Self {
+            value: value.clamp(0.0, 1.0),
+            dragging: false,
+            changed: false,
+        }
+    }
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+    //ignored mid drag so the handle doesn't fight the mouse
+    pub fn set_value(&mut self, value: f64) {
+        if !self.dragging {
+            self.value = value.clamp(0.0, 1.0);
+        }
+    }
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+}
+
+impl Element for Slider {
+    fn render(&mut self, renderer: &mut Renderer, input: &Input, panel: &mut Panel, _delta_time: f64) {
+        self.changed = false;
+
+        let bounds = Rect::new(
+            panel.bounds.x as i32 + padding() / 2,
+            panel.bounds.y as i32 + panel.y_offset,
+            panel.bounds.width as u32 - padding() as u32,
+            self.bounds().0 as u32,
+        );
+
+        let (x, y) = input.get_mouse_pos();
+
+        let hovering = x > bounds.left() && x < bounds.right() && y > bounds.top() && y < bounds.bottom();
+
+        if input.just_pressed_mouse(&MouseButton::Left) && hovering {
+            self.dragging = true;
+            //the slider owns this drag, not the panel
+            panel.drag_origin = None;
+        }
+
+        if !input.held_mouse(&MouseButton::Left) {
+            self.dragging = false;
+        }
+
+        if self.dragging {
+            let value = ((x - bounds.left()) as f64 / bounds.width() as f64).clamp(0.0, 1.0);
+            self.changed = value != self.value;
+            self.value = value;
+        }
+
+        //track
+        renderer.set_color(70, 70, 70, 255);
+        renderer.fill_rect(bounds.x, bounds.y + bounds.height() as i32 / 2 - scaled(2), bounds.width() as i32, scaled(4));
+
+        //handle
+        let handle_x = bounds.x + (self.value * bounds.width() as f64) as i32;
+
+        if self.dragging {
+            renderer.set_color(200, 200, 200, 255);
+        } else {
+            renderer.set_color(140, 140, 140, 255);
+        }
+        renderer.fill_rect(handle_x - scaled(4), bounds.y, scaled(8), bounds.height() as i32);
+    }
+
+    fn bounds(&mut self) -> (i32, i32) {
+        (scaled(20), i32::MAX)
+    }
+}
@@ -1,8 +1,8 @@
 use std::i32;
 
-use crate::{renderer::Renderer, utils::input::Input};
+use crate::{renderer::{font_manager::DEFAULT_FONT, Renderer}, utils::input::Input};
 
-use super::{Element, Panel, PADDING};
+use super::{padding, scaled, Element, Panel};
 
 const SIZES: [u8; 4] = [
     16,
@@ -23,27 +23,25 @@ impl Text {
             content: content.to_string()
         }
     }
+    //x_offset is in pixels from where the panel would put it
+    pub fn render_offset(&mut self, renderer: &mut Renderer, panel: &Panel, x_offset: i32) {
+        let line_height = scaled(SIZES[self.size].into());
+
+        renderer.draw_text(
+            DEFAULT_FONT,
+            &self.content,
+            line_height,
+            panel.bounds.x as i32 + padding() / 2 + x_offset, 
+            panel.bounds.y as i32 + panel.y_offset - line_height / 5,
+        );
+    }
 }
 
 impl Element for Text {
     fn bounds(&mut self) -> (i32, i32) {
-        (Into::<i32>::into(SIZES[self.size]) * 3/5, i32::MAX)
+        (scaled(SIZES[self.size].into()) * 3/5, i32::MAX)
     }
     fn render(&mut self, renderer: &mut Renderer, _input: &Input, panel: &mut Panel, _delta_time: f64) {
-        let line_height = SIZES[self.size].into();
-
-        let texture = unsafe {
-            renderer.get_text_texture("open_sans", &self.content, line_height)
-        };
-    
-        renderer.render_text_texture(
-            &texture, 
-            panel.bounds.x as i32 + PADDING as i32 / 2, 
-            panel.bounds.y as i32 + panel.y_offset - line_height / 5,
-        );     
-
-        unsafe {
-            renderer.free_last_texture();
-        }
+        self.render_offset(renderer, panel, 0);
     }
 }
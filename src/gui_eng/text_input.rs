@@ -2,9 +2,9 @@ use std::{f64, u32};
 
 use sdl2::{keyboard::Keycode, mouse::MouseButton, rect::Rect};
 
-use crate::{renderer::Renderer, utils::input::Input};
+use crate::{renderer::{font_manager::DEFAULT_FONT, Renderer}, utils::input::Input};
 
-use super::{Element, Panel, PADDING};
+use super::{padding, scaled, Element, Panel};
 
 pub struct TextInput {
     content: String,
@@ -12,6 +12,9 @@ pub struct TextInput {
     counter: f64,
     focused: bool,
     updated: bool,
+    //lost focus this frame, by clicking away or pressing enter
+    committed: bool,
+    font: &'static str,
 }
 
 impl TextInput {
@@ -22,8 +25,14 @@ impl TextInput {
             counter: 0.0,
             focused: false,
             updated: false,
+            committed: false,
+            font: DEFAULT_FONT,
         }
     }
+    pub fn with_font(mut self, font: &'static str) -> Self {
+        self.font = font;
+        self
+    }
     pub fn get_value(&self) -> String {
         self.content.clone()
     }
@@ -33,16 +42,24 @@ impl TextInput {
     pub fn updated(&self) -> bool {
         self.updated
     }
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+    pub fn committed(&self) -> bool {
+        self.committed
+    }
 }
 
 impl Element for TextInput {
     fn render(&mut self, renderer: &mut Renderer, input: &Input, panel: &mut Panel, delta_time: f64) {
         self.counter += delta_time;
 
+        let was_focused = self.focused;
+
         let bounds = Rect::new(
-            panel.bounds.x as i32 + PADDING as i32 / 2,
+            panel.bounds.x as i32 + padding() / 2,
             panel.bounds.y as i32 + panel.y_offset,
-            panel.bounds.width as u32 - PADDING,
+            panel.bounds.width as u32 - padding() as u32,
             self.bounds().0 as u32,
         );
             
@@ -72,16 +89,24 @@ impl Element for TextInput {
             }
         }
 
-        let text_height = (bounds.height() - PADDING / 2) as i32;
+        if input.just_pressed(&Keycode::RETURN) {
+            self.focused = false;
+        }
+
+        self.committed = was_focused && !self.focused;
+
+        if self.focused {
+            input.set_text_focus();
+        }
+
+        let text_height = bounds.height() as i32 - padding() / 2;
 
         //text texture
         let rendering_text = if placeholder { &self.placeholder } else { &self.content };
         let rendering_color = if placeholder { (205, 205, 205, 205) } else { (255, 255, 255, 255) };
 
         renderer.set_color(rendering_color.0, rendering_color.1, rendering_color.2, rendering_color.3);
-        let text_texture = unsafe {
-            renderer.get_text_texture("open_sans", rendering_text, text_height)
-        };
+        let text_size = renderer.text_size(self.font, rendering_text, text_height);
         
         //background
         renderer.set_color(70, 70, 70, 255);  
@@ -94,16 +119,14 @@ impl Element for TextInput {
         }
 
         //text
-        renderer.render_text_texture(
-            &text_texture, 
-            (bounds.x() as f64 + PADDING as f64 / 4.0) as i32,
-            (bounds.y() as f64 + PADDING as f64 / 4.0) as i32,
+        renderer.draw_text(
+            self.font,
+            rendering_text,
+            text_height,
+            (bounds.x() as f64 + padding() as f64 / 4.0) as i32,
+            (bounds.y() as f64 + padding() as f64 / 4.0) as i32,
         );
 
-        unsafe {
-            renderer.free_last_texture();
-        }
-
         if self.updated {
             self.counter = 0.0;
         }
@@ -117,14 +140,14 @@ impl Element for TextInput {
             renderer.set_color(gray, gray, gray, 255);  
 
             renderer.fill_rect(
-                bounds.x() + PADDING as i32 / 4 + if placeholder { 0 } else { text_texture.size.0 } as i32,
-                bounds.y() + PADDING as i32 / 4 + 2,
-                1,
-                text_height as i32 - 4,
+                bounds.x() + padding() / 4 + if placeholder { 0 } else { text_size.0 } as i32,
+                bounds.y() + padding() / 4 + scaled(2),
+                scaled(1),
+                text_height - scaled(4),
             );
         }
     }
     fn bounds(&mut self) -> (i32, i32) {
-        (28, i32::MAX)
+        (scaled(28), i32::MAX)
     }
 }
@@ -1,6 +1,6 @@
 use crate::renderer::Renderer;
 use crate::Input;
-use super::{button::ClickElement, Element, Panel, PADDING};
+use super::{button::ClickElement, padding, Element, Panel};
 
 pub struct ToggleGroup {
     toggles: Vec<ClickElement>,
@@ -56,6 +56,6 @@ impl Element for ToggleGroup {
     }
 
     fn bounds(&mut self) -> (i32, i32) {
-        (-(PADDING as i32 / 2), i32::MAX)
+        (-(padding() / 2), i32::MAX)
     }
 }
@@ -0,0 +1,103 @@
+use std::{fs::File, io::{self, BufWriter, Write}};
+
+use chsl::physics::{bounding_box::BoundingBox, world::PhysicsWorld};
+
+use crate::{args::Args, simulation::{self, timeline::Timeline, Simulation}, utils::scene};
+
+//steps a scene with no window or renderer, writing body states as csv
+pub fn run(args: &Args) -> Result<(), String> {
+    let path = args.scene.as_ref().ok_or("--headless needs a scene to load")?;
+    let (mut physics_world, mut bounds) = scene::load_world(path)?;
+
+    if let Some((x, y, width, height)) = args.bounds {
+        simulation::rebuild_with_bounds(&mut physics_world, BoundingBox { x, y, width, height });
+        bounds = args.bounds;
+    }
+
+    let mut simulation = Simulation::new();
+    simulation.step_size = args.step_size;
+    simulation.substeps = args.substeps;
+    simulation.paused = true;
+    //nothing is going to rewind
+    simulation.timeline = Timeline::new(0);
+
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(|e| e.to_string())?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+
+    writeln!(out, "step,id,x,y,rotation,velocity_x,velocity_y,ang_velocity").map_err(|e| e.to_string())?;
+
+    if args.trace {
+        write_state(&mut out, &mut physics_world, simulation.steps_taken()).map_err(|e| e.to_string())?;
+    }
+
+    for _ in 0..args.steps {
+        simulation.step(1);
+        simulation.update(&mut physics_world, 0.0);
+
+        if args.trace {
+            write_state(&mut out, &mut physics_world, simulation.steps_taken()).map_err(|e| e.to_string())?;
+        }
+    }
+
+    if !args.trace {
+        write_state(&mut out, &mut physics_world, simulation.steps_taken()).map_err(|e| e.to_string())?;
+    }
+
+    out.flush().map_err(|e| e.to_string())?;
+
+    if let Some(path) = &args.save {
+        scene::save_world(&physics_world, bounds, path)?;
+    }
+
+    Ok(())
+}
+
+fn write_state(out: &mut dyn Write, physics_world: &mut PhysicsWorld, step: u64) -> io::Result<()> {
+    let bodies = physics_world.all_bodies();
+
+    //hashmap order changes between runs, sorting keeps traces diffable
+    let mut ids: Vec<&String> = bodies.keys().collect();
+    ids.sort();
+
+    for id in ids {
+        let body = &bodies[id];
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{}",
+            step,
+            csv_field(id),
+            body.position.x,
+            body.position.y,
+            body.rotation,
+            body.velocity.x,
+            body.velocity.y,
+            body.ang_velocity,
+        )?;
+    }
+
+    Ok(())
+}
+
+//ids are free text, anything with a separator, quote or newline in it gets quoted
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("7"), "7");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+}
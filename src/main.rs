@@ -2,116 +2,194 @@ mod gui_eng;
 mod gui;
 mod utils;
 mod renderer;
+mod simulation;
+mod args;
+mod headless;
+mod debug_render;
 
 use utils::input::Input;
-use renderer::Renderer;
+use renderer::{font_manager::FontSource, Renderer};
 use gui::AppUI;
-use utils::TimerUtil;
-use std::{f64::{self, consts::PI}, time::{SystemTime, UNIX_EPOCH}};
-use chsl::{math::{matrix::Matrix, vector2::Vector2}, physics::{bounding_box::BoundingBox, rigidbody::Collider, world::PhysicsWorld}};
-
-pub fn loop_with_dt<F: FnMut(f64) -> bool>(mut tick: F) {
-    let mut last_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+use simulation::Simulation;
+use args::{Args, USAGE};
+use utils::{frame_limiter::FrameLimiter, scene, TimerUtil};
+use debug_render::DebugRender;
+use chsl::physics::{bounding_box::BoundingBox, world::PhysicsWorld};
 
+pub fn loop_with_dt<F: FnMut(f64, &mut FrameLimiter) -> bool>(limiter: &mut FrameLimiter, mut tick: F) {
     loop { 
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-        let dt = now - last_time;
-        last_time = now;
+        let dt = limiter.next_frame();
 
-        if !tick(dt.as_secs_f64()) {
+        if !tick(dt, limiter) {
             return;
         }
     }
 }
 
-trait DebugRender {
-    fn debug_render(&mut self, renderer: &mut Renderer);
-}
-
-impl DebugRender for PhysicsWorld {
-    fn debug_render(&mut self, renderer: &mut Renderer) {
-        for (_, body) in self.all_bodies().iter_mut() {
-            let mut points = vec![];
-
-            match &body.collider {
-                Collider::Circle { radius } => {
-                    let mut angle: f64 = 0.0;
-                    let iterations: f64 = 16.0;
-
-                    for _ in 0..iterations as usize {
-                        angle += PI * 2.0 / iterations;
-                        points.push(Vector2::new(angle.sin() * radius, angle.cos() * radius))
-                    }
-                }
-
-                Collider::Polygon { vertices } => {
-                    points.append(&mut vertices.clone()); 
-                }
-            }
-
-            let transform = Matrix::new().scale(body.scale).rot(body.rotation);
-            
-            for point in points.iter_mut() {
-                *point = transform.vec_mul(point);
-            }
-            
-            let mut last = &points[points.len()-1];
-            
-            for i in 0..points.len() {
-                let current = &points[i];
-                
-                renderer.set_color(0, 0, 0, 255);
-
-                let a = *last + body.position;
-                let b = *current + body.position;
-
-                renderer.line(a.x as i32, a.y as i32, b.x as i32, b.y as i32);
+fn main() {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(1);
+        }
+    };
 
-                last = current;
-            }
+    if args.headless {
+        if let Err(e) = headless::run(&args) {
+            eprintln!("{}", e);
+            std::process::exit(1);
         }
+        return;
+    }
+
+    if args.offscreen {
+        //events still need a video driver, this one opens nothing
+        sdl2::hint::set("SDL_VIDEODRIVER", "dummy");
     }
-}
 
-fn main() {
     let sdl_context = sdl2::init().unwrap();  
 
     let mut input = Input::new(sdl_context.event_pump().unwrap());
 
-    let mut physics_world = PhysicsWorld::new(BoundingBox {
-        x: 0.0,
-        y: 0.0,
-        width: 1240.0,
-        height: 880.0,
-    });
+    let (x, y, width, height) = args.world_bounds();
+
+    let (mut physics_world, mut bounds) = match &args.scene {
+        Some(path) => scene::load_world(path).unwrap_or_else(|e| {
+            eprintln!("Unable to load {}: {}", path, e);
+            std::process::exit(1);
+        }),
+        None => (PhysicsWorld::new(BoundingBox { x, y, width, height }), Some((x, y, width, height))),
+    };
+
+    //a loaded scene keeps its own bounds unless they're given
+    if args.scene.is_some() && args.bounds.is_some() {
+        simulation::rebuild_with_bounds(&mut physics_world, BoundingBox { x, y, width, height });
+        bounds = args.bounds;
+    }
     
-    let mut ui = AppUI::new();
+    let mut simulation = Simulation::new();
+    simulation.paused = args.paused;
+    simulation.step_size = args.step_size;
+    simulation.substeps = args.substeps;
+    simulation.set_time_scale(args.time_scale);
+
+    let mut renderer = if args.offscreen {
+        Renderer::offscreen(args.width, args.height)
+    } else {
+        Renderer::new(args.width, args.height, args.vsync)
+    };
+
+    for (name, path, size) in &args.fonts {
+        if let Err(e) = renderer.register_font(name, &FontSource::File(path.clone()), *size) {
+            eprintln!("Unable to load font {} from {}: {}", name, path, e);
+            std::process::exit(1);
+        }
+    }
+
+    let mut dpi_scale = renderer.dpi_scale();
+    gui_eng::set_ui_scale(args.ui_scale.unwrap_or(dpi_scale));
 
-    let mut renderer = Renderer::new();
+    let mut ui = AppUI::new(&mut simulation, bounds);
 
     let mut timer_util = TimerUtil::new();
+    timer_util.log = args.log_timings;
 
-    loop_with_dt(| delta_time | {
-        renderer.clear(255, 255, 255, 255);
+    if args.chrome_trace.is_some() {
+        timer_util.start_trace();
+    }
+
+    if let Some(path) = &args.record_frames {
+        if let Err(e) = ui.start_frame_recording(path, renderer.size, args.record_every) {
+            eprintln!("Unable to record frames to {}: {}", path, e);
+            std::process::exit(1);
+        }
+    }
+
+    let mut limiter = FrameLimiter::new(args.fps, args.idle_fps);
+    let mut frames = 0;
 
-        if input.close_button() {
+    loop_with_dt(&mut limiter, | delta_time, limiter | {
+        if input.close_button() || args.frames.is_some_and(|max| frames >= max) {
             return false;
         }
+        frames += 1;
+
+        timer_util.start("frame");
+
+        timer_util.start("input");
+        let delta_time = input.update(delta_time);
+        timer_util.stop("input");
 
-        input.update();
+        if input.window_changed() {
+            renderer.resize();
+        }
+
+        //keeps whatever the user picked on top of the dpi, relative to the new display
+        if input.display_changed() && renderer.dpi_scale() != dpi_scale {
+            gui_eng::set_ui_scale(gui_eng::ui_scale() * renderer.dpi_scale() / dpi_scale);
+            dpi_scale = renderer.dpi_scale();
+        }
+
+        //before anything in world space is drawn, so it all uses this frame's view
+        renderer.camera.update(&input, ui.mouse_over_ui(&input));
+
+        renderer.clear(255, 255, 255, 255);
+        debug_render::grid::render(&mut renderer, &ui.debug_render_settings().grid);
+
+        //nothing is moving and nobody is touching anything
+        limiter.idle = simulation.paused && !input.any_events();
 
         timer_util.start("UI");
-        ui.render(&mut physics_world, &mut renderer, &input, delta_time);
-        timer_util.stop_log_secs("UI");
+        ui.render(&mut physics_world, &mut simulation, &mut renderer, &mut input, &mut timer_util, delta_time);
+        timer_util.stop("UI");
         
         timer_util.start("physics");
-        physics_world.update(delta_time, 1);
-        timer_util.stop_log_secs("physics");
+        simulation.update(&mut physics_world, delta_time);
+        timer_util.stop("physics");
+
+        ui.update_input_recording(&mut physics_world, &mut simulation, &mut renderer, &mut input);
+
+        timer_util.start("render");
+        physics_world.debug_render(&mut renderer, ui.debug_render_settings());
 
-        physics_world.debug_render(&mut renderer);
+        if let Some(screenshot) = ui.take_screenshot() {
+            let settings = ui.debug_render_settings();
+
+            let captured = screenshot.capture(&mut renderer, |renderer| {
+                renderer.clear(255, 255, 255, 255);
+                debug_render::grid::render(renderer, &settings.grid);
+                physics_world.debug_render(renderer, settings);
+            });
+
+            match captured {
+                Ok(()) => println!("Saved screenshot to {}", screenshot.path),
+                Err(e) => println!("Unable to save screenshot: {}", e),
+            }
+        }
+
+        if let Some(recorder) = ui.frame_recorder() {
+            if let Err(e) = recorder.capture(&mut renderer, delta_time) {
+                println!("Unable to record frame: {}", e);
+                ui.stop_frame_recording();
+            }
+        }
        
         renderer.update();
+        timer_util.stop("render");
+
+        timer_util.stop("frame");
+        timer_util.end_frame();
 
         true
-    })
+    });
+
+    ui.stop_frame_recording();
+
+    //the profiler panel may have already stopped and written it
+    if let Some(path) = args.chrome_trace.as_ref().filter(|_| timer_util.tracing()) {
+        if let Err(e) = timer_util.stop_trace(path) {
+            eprintln!("Unable to write trace to {}: {}", path, e);
+        }
+    }
 }
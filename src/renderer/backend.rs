@@ -0,0 +1,34 @@
+use sdl2::{pixels::Color, surface::Surface};
+
+//screen space corner of a filled triangle, colours blend across the face
+#[derive(Clone, Copy)]
+pub struct Vertex {
+    pub x: f32,
+    pub y: f32,
+    pub color: Color,
+}
+
+//a string the backend has uploaded, only the backend knows what it really is
+pub type TextHandle = u64;
+
+//everything the renderer needs from whatever it is drawing into, all in screen pixels
+//colours are alpha blended
+pub trait RenderBackend {
+    fn size(&self) -> (u32, u32);
+    fn set_color(&mut self, color: Color);
+    fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32);
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32);
+    fn outline_rect(&mut self, x: i32, y: i32, width: i32, height: i32);
+    //every three vertices are one triangle, either winding
+    fn fill_triangles(&mut self, vertices: &[Vertex]);
+    //how much bigger than a 96 dpi display this one is
+    fn dpi_scale(&self) -> f64;
+    //the renderer rasterizes, the backend keeps it until it's freed
+    fn upload_text(&mut self, surface: &Surface) -> Result<TextHandle, String>;
+    //stretched to fill the rect
+    fn draw_text(&mut self, text: TextHandle, x: i32, y: i32, width: u32, height: u32);
+    fn free_text(&mut self, text: TextHandle);
+    //rgba, row major, whatever has been drawn since the last present
+    fn read_pixels(&mut self) -> Result<Vec<u8>, String>;
+    fn present(&mut self);
+}
@@ -0,0 +1,94 @@
+use chsl::math::vector2::Vector2;
+use sdl2::mouse::MouseButton;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::input::Input;
+
+const MIN_ZOOM: f64 = 0.05;
+const MAX_ZOOM: f64 = 50.0;
+const ZOOM_STEP: f64 = 1.1;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Camera {
+    //world point drawn at the top left of the screen
+    pub position: Vector2,
+    pub zoom: f64,
+    pan_from: Option<(i32, i32)>,
+}
+
+impl Camera {
+    pub fn new() -> Self {
+        Self {
+            position: Vector2::zero(),
+            zoom: 1.0,
+            pan_from: None,
+        }
+    }
+
+    pub fn world_to_screen(&self, point: Vector2) -> Vector2 {
+        Vector2::new(
+            (point.x - self.position.x) * self.zoom,
+            (point.y - self.position.y) * self.zoom,
+        )
+    }
+
+    pub fn screen_to_world(&self, x: f64, y: f64) -> Vector2 {
+        Vector2::new(
+            x / self.zoom + self.position.x,
+            y / self.zoom + self.position.y,
+        )
+    }
+
+    //keeps whatever is under the screen point in place
+    pub fn zoom_at(&mut self, x: f64, y: f64, factor: f64) {
+        let anchor = self.screen_to_world(x, y);
+
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.position = Vector2::new(anchor.x - x / self.zoom, anchor.y - y / self.zoom);
+    }
+
+    pub fn center_on(&mut self, point: Vector2, screen_size: (u32, u32)) {
+        self.position = Vector2::new(
+            point.x - screen_size.0 as f64 / 2.0 / self.zoom,
+            point.y - screen_size.1 as f64 / 2.0 / self.zoom,
+        );
+    }
+
+    pub fn resize(&mut self, old_size: (u32, u32), new_size: (u32, u32)) {
+        self.position = Vector2::new(
+            self.position.x - (new_size.0 as f64 - old_size.0 as f64) / 2.0 / self.zoom,
+            self.position.y - (new_size.1 as f64 - old_size.1 as f64) / 2.0 / self.zoom,
+        );
+    }
+
+    pub fn reset(&mut self) {
+        self.position = Vector2::zero();
+        self.zoom = 1.0;
+    }
+
+    //middle drag pans, the wheel zooms on the cursor
+    //neither starts over the ui, a pan that's already going carries on across it
+    pub fn update(&mut self, input: &Input, over_ui: bool) {
+        let (x, y) = input.get_mouse_pos();
+
+        if input.just_pressed_mouse(&MouseButton::Middle) && !over_ui {
+            self.pan_from = Some((x, y));
+        }
+
+        if !input.held_mouse(&MouseButton::Middle) {
+            self.pan_from = None;
+        }
+
+        if let Some((from_x, from_y)) = self.pan_from {
+            self.position = Vector2::new(
+                self.position.x - (x - from_x) as f64 / self.zoom,
+                self.position.y - (y - from_y) as f64 / self.zoom,
+            );
+            self.pan_from = Some((x, y));
+        }
+
+        if input.wheel() != 0 && !over_ui {
+            self.zoom_at(x as f64, y as f64, ZOOM_STEP.powi(input.wheel()));
+        }
+    }
+}
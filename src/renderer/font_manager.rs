@@ -1,9 +1,22 @@
 use std::collections::HashMap;
-use sdl2::ttf::{Font, Sdl2TtfContext};
+use sdl2::{pixels::Color, rwops::RWops, surface::Surface, ttf::{Font, Sdl2TtfContext}};
+
+//shipped inside the binary so it doesn't matter where it's run from
+const EMBEDDED_FONT: &[u8] = include_bytes!("../assets/OpenSans.ttf");
+const DEFAULT_SIZE: u16 = 128;
+
+pub const DEFAULT_FONT: &str = "open_sans";
+//number fields ask for this, it's the default face until something else is registered
+pub const NUMERIC_FONT: &str = "numeric";
+
+pub enum FontSource {
+    Embedded(&'static [u8]),
+    File(String),
+}
 
 pub struct FontManager {
     ttf_context: Sdl2TtfContext,
-    fonts: HashMap<&'static str, Font<'static, 'static>>,
+    fonts: HashMap<String, Font<'static, 'static>>,
 }
 
 impl Drop for FontManager {
@@ -16,23 +29,58 @@ impl Drop for FontManager {
 }
 
 impl FontManager {
-    unsafe fn load_font(ttf_context: *mut Sdl2TtfContext) -> Font<'static, 'static> {
-        (*ttf_context).load_font("src/assets/OpenSans.ttf", 128).unwrap()
+    unsafe fn load_font(ttf_context: *const Sdl2TtfContext, source: &FontSource, size: u16) -> Result<Font<'static, 'static>, String> {
+        match source {
+            FontSource::Embedded(data) => (*ttf_context).load_font_from_rwops(RWops::from_bytes(data)?, size),
+            FontSource::File(path) => (*ttf_context).load_font(path, size),
+        }
     }
 
-    pub fn get_font(&mut self, key: &str) -> &Font<'static, 'static> {
-        self.fonts.get(key).unwrap()
+    //replaces any font already registered under the name
+    pub fn register(&mut self, name: &str, source: &FontSource, size: u16) -> Result<(), String> {
+        let font = unsafe {
+            FontManager::load_font(&self.ttf_context, source, size)?
+        };
+
+        self.fonts.insert(name.to_string(), font);
+        Ok(())
     }
 
-    pub fn new() -> FontManager {
-        let mut ttf_context = sdl2::ttf::init().map_err(|e| e.to_string()).unwrap();
-        let open_sans = unsafe {
-            FontManager::load_font(&mut ttf_context)
+    pub fn get_font(&self, key: &str) -> Result<&Font<'static, 'static>, String> {
+        self.fonts
+            .get(key)
+            .ok_or(format!("no font registered as {}", key))
+    }
+
+    //white text at the loaded size, backends scale and tint it themselves
+    pub fn rasterize(&mut self, font: &str, text: &str) -> Result<Surface<'static>, String> {
+        let font_handle = match self.get_font(font) {
+            Ok(font_handle) => font_handle,
+            Err(e) => {
+                eprintln!("{}, using {}", e, DEFAULT_FONT);
+                self.get_font(DEFAULT_FONT)?
+            }
         };
 
-        FontManager {
-            fonts: HashMap::from([("open_sans", open_sans)]),
+        //ttf refuses to render nothing, a space keeps the height right
+        font_handle
+            .render(if text.is_empty() { " " } else { text })
+            .blended(Color::RGBA(255, 255, 255, 255))
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn new() -> FontManager {
+        let ttf_context = sdl2::ttf::init().map_err(|e| e.to_string()).unwrap();
+
+        let mut font_manager = FontManager {
+            fonts: HashMap::new(),
             ttf_context,
+        };
+
+        for name in [DEFAULT_FONT, NUMERIC_FONT] {
+            font_manager.register(name, &FontSource::Embedded(EMBEDDED_FONT), DEFAULT_SIZE).unwrap();
         }
+
+        font_manager
     }
 }
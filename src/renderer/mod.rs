@@ -1,116 +1,189 @@
-mod font_manager;
-
-use std::{f64, u8};
-
-use font_manager::FontManager;
-use sdl2::{pixels::Color, rect::{Point, Rect}, render::{Canvas, Texture, TextureCreator, TextureQuery}, video::{Window, WindowContext}};
+pub mod font_manager;
+mod primitives;
+mod text_cache;
+pub mod backend;
+pub mod camera;
+pub mod offscreen;
+pub mod window;
+
+use std::{collections::HashSet, f64, u8};
+
+use backend::{RenderBackend, TextHandle};
+use camera::Camera;
+use chsl::math::vector2::Vector2;
+use font_manager::{FontManager, FontSource};
+use offscreen::OffscreenBackend;
+use sdl2::pixels::Color;
+use text_cache::TextCache;
+use window::WindowBackend;
 
 pub struct Renderer {
-    canvas: Canvas<Window>,
+    backend: Box<dyn RenderBackend>,
     pub size: (u32, u32),
-    texture_creator: TextureCreator<WindowContext>,
-    font_manager: FontManager,
-    textures: Vec<Texture>,
+    pub camera: Camera,
     color: Color,
-}
-
-pub struct TextTexure {
-    pub size: (u32, u32),
-    pub texture: usize,
+    //only the primitives honour these, line and the rects are always one hard pixel
+    line_width: f64,
+    antialias: bool,
+    //text is rasterized here once, whatever backend is in use only uploads and draws it
+    font_manager: FontManager,
+    text_cache: TextCache,
+    //each failure is printed once, not every frame the text is drawn
+    text_errors: HashSet<String>,
 }
 
 impl Renderer {
-    pub fn new() -> Self {
-        let sdl_context = sdl2::init().unwrap();
-       
-        // canvas
-        let video_subsystem = sdl_context.video().unwrap();
-        
-        let window = video_subsystem
-            .window("SDL2", 1240, 880)
-            .position_centered()
-            .build()
-            .map_err(|e| e.to_string())
-            .unwrap();
-        let size = window.size();
-        
-        let canvas = window
-            .into_canvas()
-            .accelerated()
-            .build()
-            .map_err(|e| e.to_string())
-            .unwrap();
-       
-        // text
-        let texture_creator = canvas.texture_creator();
+    pub fn new(width: u32, height: u32, vsync: bool) -> Self {
+        Self::with_backend(Box::new(WindowBackend::new(width, height, vsync)))
+    }
 
+    //for running without a display, nothing is shown
+    pub fn offscreen(width: u32, height: u32) -> Self {
+        Self::with_backend(Box::new(OffscreenBackend::new(width, height)))
+    }
+
+    pub fn with_backend(backend: Box<dyn RenderBackend>) -> Self {
         Self {
-            texture_creator,
-            font_manager: FontManager::new(),
-            textures: vec![],
-            canvas,
+            size: backend.size(),
+            backend,
+            camera: Camera::new(),
             color: Color::RGBA(255, 255, 255, 255),
-            size,
+            line_width: 1.0,
+            antialias: false,
+            font_manager: FontManager::new(),
+            text_cache: TextCache::new(),
+            text_errors: HashSet::new(),
         }
     }
 
-    pub fn set_color(&mut self, r: u8, b: u8, g: u8, a: u8) {
+    //picks up a new window size, whatever was in the middle of the view stays there
+    pub fn resize(&mut self) {
+        let size = self.backend.size();
+        self.camera.resize(self.size, size);
+        self.size = size;
+    }
+
+    pub fn set_color(&mut self, r: u8, g: u8, b: u8, a: u8) {
         self.color = Color::RGBA(r, g, b, a);
-        self.canvas.set_draw_color(self.color);
+        self.backend.set_color(self.color);
     }
     
     pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32) {
-        self.canvas.fill_rect(Rect::new(x, y, width as u32, height as u32)).unwrap();
+        self.backend.fill_rect(x, y, width, height);
     }
 
     pub fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
-        self.canvas.draw_line(Point::new(x1, y1), Point::new(x2, y2)).unwrap();
+        self.backend.line(x1, y1, x2, y2);
     }
     
+    //line between two world points, through the camera
+    pub fn world_line(&mut self, a: Vector2, b: Vector2) {
+        let a = self.camera.world_to_screen(a);
+        let b = self.camera.world_to_screen(b);
+        self.segment(a, b);
+    }
+
     pub fn outline_rect(&mut self, x: i32, y: i32, width: i32, height: i32) {
-        self.canvas.draw_rect(Rect::new(x, y, width as u32, height as u32)).unwrap();
+        self.backend.outline_rect(x, y, width, height);
     }
     
-    pub fn clear(&mut self, r: u8, b: u8, g: u8, a: u8) {
-        self.canvas.set_draw_color(Color::RGBA(r, g, b, a));
-        let rect = Rect::new(0, 0, self.size.0, self.size.1);
-        self.canvas.fill_rect(rect).unwrap();
+    //fills the backend's current size, it can be ahead of ours for a frame after a resize
+    pub fn clear(&mut self, r: u8, g: u8, b: u8, a: u8) {
+        let (width, height) = self.backend.size();
+        self.backend.set_color(Color::RGBA(r, g, b, a));
+        self.backend.fill_rect(0, 0, width as i32, height as i32);
+        self.backend.set_color(self.color);
     }
 
-    pub unsafe fn get_text_texture(&mut self, font: &str, text: &String, line_height: i32) -> TextTexure {
-        let font_handle = self.font_manager.get_font(font);
+    pub fn dpi_scale(&self) -> f64 {
+        self.backend.dpi_scale()
+    }
 
-        let surface = font_handle
-            .render(text)
-            .blended(Color::RGBA(255, 255, 255, 255))
-            .map_err(|e| e.to_string()).unwrap();
+    //the name can then be passed to text_size and draw_text
+    pub fn register_font(&mut self, name: &str, source: &FontSource, size: u16) -> Result<(), String> {
+        self.font_manager.register(name, source, size)?;
+        self.text_cache.remove_font(name, self.backend.as_mut());
+        Ok(())
+    }
 
-        let texture = self.texture_creator
-            .create_texture_from_surface(&surface)
-            .map_err(|e| e.to_string()).unwrap();
+    //rasterizes and uploads on a miss, otherwise hands back the string from an earlier frame
+    fn cached_text(&mut self, font: &str, text: &str) -> Result<(TextHandle, (u32, u32)), String> {
+        if let Some(cached) = self.text_cache.get(font, text) {
+            return Ok((cached.handle, cached.size));
+        }
 
-        let TextureQuery { width, height, .. } = texture.query();
+        let surface = self.font_manager.rasterize(font, text)?;
+        let handle = self.backend.upload_text(&surface)?;
+        let cached = self.text_cache.insert(font, text, handle, surface.size(), self.backend.as_mut());
 
-        let aspect = width as f64 / height as f64;
+        Ok((cached.handle, cached.size))
+    }
 
-        self.textures.push(texture);
+    //text that can't be drawn takes no room rather than taking the app down
+    fn report_text_error(&mut self, e: String) {
+        if !self.text_errors.contains(&e) {
+            println!("Unable to draw text: {}", e);
+            self.text_errors.insert(e);
+        }
+    }
 
-        TextTexure {
-            texture: self.textures.len() - 1,
-            size: ((line_height as f64 * aspect) as u32, line_height as u32),
+    //size the text will be drawn at for a line height
+    pub fn text_size(&mut self, font: &str, text: &str, line_height: i32) -> (u32, u32) {
+        match self.cached_text(font, text) {
+            Ok((_, size)) => fit_line_height(size, line_height),
+            Err(e) => {
+                self.report_text_error(e);
+                (0, line_height as u32)
+            }
         }
     }
 
-    pub fn render_text_texture(&mut self, text_texture: &TextTexure, x: i32, y: i32) {
-        self.canvas.copy(&self.textures[text_texture.texture], None, Rect::new(x, y, text_texture.size.0, text_texture.size.1)).unwrap();
+    pub fn draw_text(&mut self, font: &str, text: &str, line_height: i32, x: i32, y: i32) -> (u32, u32) {
+        let (handle, size) = match self.cached_text(font, text) {
+            Ok(cached) => cached,
+            Err(e) => {
+                self.report_text_error(e);
+                return (0, line_height as u32);
+            }
+        };
+
+        let size = fit_line_height(size, line_height);
+        self.backend.draw_text(handle, x, y, size.0, size.1);
+
+        size
+    }
+
+    //draws into memory the size of the window instead, for pictures that shouldn't be shown or recorded
+    pub fn render_offscreen(&mut self, draw: impl FnOnce(&mut Renderer)) -> Result<Vec<u8>, String> {
+        let backend = std::mem::replace(&mut self.backend, Box::new(OffscreenBackend::new(self.size.0, self.size.1)));
+        //cached text belongs to the backend that uploaded it
+        let text_cache = std::mem::replace(&mut self.text_cache, TextCache::new());
+
+        self.backend.set_color(self.color);
+        draw(self);
+        let pixels = self.backend.read_pixels();
+
+        self.backend = backend;
+        self.text_cache = text_cache;
+        self.backend.set_color(self.color);
+
+        pixels
     }
 
-    //indexing is used rn so yeah
-    pub unsafe fn free_last_texture(&mut self) {
-        self.textures.pop().unwrap().destroy()
+    //has to happen before update, the back buffer is gone after presenting
+    pub fn read_pixels(&mut self) -> Result<Vec<u8>, String> {
+        self.backend.read_pixels()
     }
 
     pub fn update(&mut self) {
-        self.canvas.present();
+        self.text_cache.end_frame(self.backend.as_mut());
+        self.backend.present();
     }
 }
+
+//rasterized text stretched to a line height, keeping its aspect
+fn fit_line_height((width, height): (u32, u32), line_height: i32) -> (u32, u32) {
+    let aspect = width as f64 / height as f64;
+
+    ((line_height as f64 * aspect) as u32, line_height as u32)
+}
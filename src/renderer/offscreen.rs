@@ -0,0 +1,298 @@
+use sdl2::{pixels::{Color, PixelFormatEnum}, surface::Surface};
+
+use super::{backend::{RenderBackend, TextHandle, Vertex}, text_cache::TextStore};
+
+//draws into an rgba buffer in memory, needs no display or gpu
+pub struct OffscreenBackend {
+    size: (u32, u32),
+    //row major, 4 bytes per pixel
+    pixels: Vec<u8>,
+    color: Color,
+    //tightly packed rgba and its size
+    texts: TextStore<(Vec<u8>, (u32, u32))>,
+}
+
+impl OffscreenBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            size: (width, height),
+            pixels: vec![0; width as usize * height as usize * 4],
+            color: Color::RGBA(255, 255, 255, 255),
+            texts: TextStore::new(),
+        }
+    }
+
+    fn plot(&mut self, x: i32, y: i32) {
+        if let Some(i) = pixel_index(self.size, x, y) {
+            let Color { r, g, b, a } = self.color;
+            blend(&mut self.pixels[i..i + 4], &[r, g, b, a]);
+        }
+    }
+
+    //pixel centres inside the triangle, colour interpolated from the corners
+    fn fill_triangle(&mut self, corners: &[Vertex]) {
+        let [a, b, c] = [corners[0], corners[1], corners[2]];
+        let edge = |from: Vertex, to: Vertex, x: f32, y: f32| (to.x - from.x) * (y - from.y) - (to.y - from.y) * (x - from.x);
+
+        let area = edge(a, b, c.x, c.y);
+
+        if area.abs() < f32::EPSILON {
+            return;
+        }
+
+        let min_x = (a.x.min(b.x).min(c.x).floor() as i32).max(0);
+        let max_x = (a.x.max(b.x).max(c.x).ceil() as i32).min(self.size.0 as i32 - 1);
+        let min_y = (a.y.min(b.y).min(c.y).floor() as i32).max(0);
+        let max_y = (a.y.max(b.y).max(c.y).ceil() as i32).min(self.size.1 as i32 - 1);
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+
+                //dividing by the area makes these positive inside for either winding
+                let weight_a = edge(b, c, px, py) / area;
+                let weight_b = edge(c, a, px, py) / area;
+                let weight_c = 1.0 - weight_a - weight_b;
+
+                if weight_a < 0.0 || weight_b < 0.0 || weight_c < 0.0 {
+                    continue;
+                }
+
+                let mix = |channel: fn(Color) -> u8| {
+                    (channel(a.color) as f32 * weight_a + channel(b.color) as f32 * weight_b + channel(c.color) as f32 * weight_c).round() as u8
+                };
+
+                let source = [mix(|color| color.r), mix(|color| color.g), mix(|color| color.b), mix(|color| color.a)];
+                let i = pixel_index(self.size, x, y).unwrap();
+
+                blend(&mut self.pixels[i..i + 4], &source);
+            }
+        }
+    }
+
+    //liang-barsky, so a line thousands of pixels off screen doesn't get walked
+    fn clip_line(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> Option<(i32, i32, i32, i32)> {
+        let (x1, y1, x2, y2) = (x1 as f64, y1 as f64, x2 as f64, y2 as f64);
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let (max_x, max_y) = (self.size.0 as f64 - 1.0, self.size.1 as f64 - 1.0);
+
+        let (mut enter, mut exit) = (0.0f64, 1.0f64);
+
+        for (p, q) in [(-dx, x1), (dx, max_x - x1), (-dy, y1), (dy, max_y - y1)] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+                continue;
+            }
+
+            let t = q / p;
+
+            if p < 0.0 {
+                enter = enter.max(t);
+            } else {
+                exit = exit.min(t);
+            }
+        }
+
+        if enter > exit {
+            return None;
+        }
+
+        Some((
+            (x1 + dx * enter).round() as i32,
+            (y1 + dy * enter).round() as i32,
+            (x1 + dx * exit).round() as i32,
+            (y1 + dy * exit).round() as i32,
+        ))
+    }
+
+}
+
+fn pixel_index(size: (u32, u32), x: i32, y: i32) -> Option<usize> {
+    if x < 0 || y < 0 || x >= size.0 as i32 || y >= size.1 as i32 {
+        return None;
+    }
+
+    Some((y as usize * size.0 as usize + x as usize) * 4)
+}
+
+//source over, what sdl's blend mode does
+fn blend(dest: &mut [u8], source: &[u8]) {
+    let alpha = source[3] as u32;
+
+    for channel in 0..3 {
+        dest[channel] = ((source[channel] as u32 * alpha + dest[channel] as u32 * (255 - alpha)) / 255) as u8;
+    }
+
+    dest[3] = (alpha + dest[3] as u32 * (255 - alpha) / 255) as u8;
+}
+
+impl RenderBackend for OffscreenBackend {
+    fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        let (start_x, end_x) = (x.max(0), (x + width.max(1)).min(self.size.0 as i32));
+        let (start_y, end_y) = (y.max(0), (y + height.max(1)).min(self.size.1 as i32));
+
+        for y in start_y..end_y {
+            for x in start_x..end_x {
+                self.plot(x, y);
+            }
+        }
+    }
+
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        let Some((mut x, mut y, x2, y2)) = self.clip_line(x1, y1, x2, y2) else {
+            return;
+        };
+
+        //bresenham, both ends included like sdl
+        let (dx, dy) = ((x2 - x).abs(), -(y2 - y).abs());
+        let (step_x, step_y) = (if x < x2 { 1 } else { -1 }, if y < y2 { 1 } else { -1 });
+        let mut error = dx + dy;
+
+        loop {
+            self.plot(x, y);
+
+            if x == x2 && y == y2 {
+                break;
+            }
+
+            let doubled = error * 2;
+
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    fn outline_rect(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        let (right, bottom) = (x + width.max(1) - 1, y + height.max(1) - 1);
+
+        self.line(x, y, right, y);
+        self.line(x, bottom, right, bottom);
+        self.line(x, y, x, bottom);
+        self.line(right, y, right, bottom);
+    }
+
+    fn fill_triangles(&mut self, vertices: &[Vertex]) {
+        for corners in vertices.chunks_exact(3) {
+            self.fill_triangle(corners);
+        }
+    }
+
+    fn dpi_scale(&self) -> f64 {
+        1.0
+    }
+
+    fn upload_text(&mut self, surface: &Surface) -> Result<TextHandle, String> {
+        let surface = surface.convert_format(PixelFormatEnum::RGBA32)?;
+
+        let (width, height) = surface.size();
+        let pitch = surface.pitch() as usize;
+        let row = width as usize * 4;
+
+        //surface rows can be padded, the store keeps them tight
+        let pixels = surface.with_lock(|data| {
+            (0..height as usize)
+                .flat_map(|y| data[y * pitch..y * pitch + row].iter().copied())
+                .collect()
+        });
+
+        Ok(self.texts.insert((pixels, (width, height))))
+    }
+
+    fn draw_text(&mut self, text: TextHandle, x: i32, y: i32, width: u32, height: u32) {
+        let Some(&(ref source, (source_width, source_height))) = self.texts.get(text) else {
+            return;
+        };
+
+        //nearest neighbour, sdl's default scale quality
+        for dy in 0..height {
+            let source_y = (dy as u64 * source_height as u64 / height as u64) as usize;
+
+            for dx in 0..width {
+                let source_x = (dx as u64 * source_width as u64 / width as u64) as usize;
+                let i = (source_y * source_width as usize + source_x) * 4;
+
+                if let Some(dest) = pixel_index(self.size, x + dx as i32, y + dy as i32) {
+                    blend(&mut self.pixels[dest..dest + 4], &source[i..i + 4]);
+                }
+            }
+        }
+    }
+
+    fn free_text(&mut self, text: TextHandle) {
+        self.texts.remove(text);
+    }
+
+    fn read_pixels(&mut self) -> Result<Vec<u8>, String> {
+        Ok(self.pixels.clone())
+    }
+
+    fn present(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(backend: &mut OffscreenBackend, x: usize, y: usize) -> [u8; 4] {
+        let pixels = backend.read_pixels().unwrap();
+        let i = (y * backend.size.0 as usize + x) * 4;
+        [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+    }
+
+    #[test]
+    fn draws_rects_lines_and_triangles() {
+        let mut backend = OffscreenBackend::new(32, 32);
+
+        backend.set_color(Color::RGBA(255, 0, 0, 255));
+        backend.fill_rect(2, 2, 4, 4);
+
+        backend.set_color(Color::RGBA(0, 255, 0, 255));
+        backend.line(0, 10, 31, 10);
+
+        let blue = Color::RGBA(0, 0, 255, 255);
+        backend.fill_triangles(&[
+            Vertex { x: 16.0, y: 16.0, color: blue },
+            Vertex { x: 30.0, y: 16.0, color: blue },
+            Vertex { x: 16.0, y: 30.0, color: blue },
+        ]);
+
+        assert_eq!(pixel(&mut backend, 2, 2), [255, 0, 0, 255]);
+        assert_eq!(pixel(&mut backend, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&mut backend, 6, 6), [0, 0, 0, 0]);
+
+        assert_eq!(pixel(&mut backend, 0, 10), [0, 255, 0, 255]);
+        assert_eq!(pixel(&mut backend, 31, 10), [0, 255, 0, 255]);
+        assert_eq!(pixel(&mut backend, 15, 11), [0, 0, 0, 0]);
+
+        assert_eq!(pixel(&mut backend, 17, 17), [0, 0, 255, 255]);
+        assert_eq!(pixel(&mut backend, 28, 28), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn blends_over_what_is_there() {
+        let mut backend = OffscreenBackend::new(4, 4);
+
+        backend.set_color(Color::RGBA(255, 255, 255, 255));
+        backend.fill_rect(0, 0, 4, 4);
+        backend.set_color(Color::RGBA(0, 0, 0, 51));
+        backend.fill_rect(0, 0, 4, 4);
+
+        assert_eq!(pixel(&mut backend, 1, 1), [204, 204, 204, 255]);
+    }
+}
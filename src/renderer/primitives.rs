@@ -0,0 +1,254 @@
+use std::f64::consts::PI;
+
+use chsl::math::vector2::Vector2;
+use sdl2::pixels::Color;
+
+use super::{backend::Vertex, Renderer};
+
+//width of the fade at antialiased edges, in pixels
+const FRINGE: f64 = 1.0;
+//furthest a circle's chords may stray from the real curve, in pixels
+const CIRCLE_TOLERANCE: f64 = 0.25;
+const MIN_CIRCLE_SEGMENTS: usize = 8;
+const MAX_CIRCLE_SEGMENTS: usize = 512;
+
+fn vertex(point: Vector2, color: Color) -> Vertex {
+    Vertex { x: point.x as f32, y: point.y as f32, color }
+}
+
+fn transparent(color: Color) -> Color {
+    Color::RGBA(color.r, color.g, color.b, 0)
+}
+
+fn scaled(point: Vector2, scale: f64) -> Vector2 {
+    Vector2::new(point.x * scale, point.y * scale)
+}
+
+//two triangles, a and b take the first colour, c and d the second
+fn quad(vertices: &mut Vec<Vertex>, a: Vector2, b: Vector2, c: Vector2, d: Vector2, inner: Color, outer: Color) {
+    vertices.extend([
+        vertex(a, inner), vertex(b, inner), vertex(c, outer),
+        vertex(a, inner), vertex(c, outer), vertex(d, outer),
+    ]);
+}
+
+//everything here is in screen space and follows set_line_width and set_antialias
+impl Renderer {
+    pub fn set_line_width(&mut self, width: f64) {
+        self.line_width = width.max(0.0);
+    }
+
+    pub fn set_antialias(&mut self, antialias: bool) {
+        self.antialias = antialias;
+    }
+
+    //enough segments that a circle this many pixels across looks round
+    pub fn circle_segments(radius: f64) -> usize {
+        if radius <= CIRCLE_TOLERANCE {
+            return MIN_CIRCLE_SEGMENTS;
+        }
+
+        let step = 2.0 * (1.0 - CIRCLE_TOLERANCE / radius).acos();
+
+        ((2.0 * PI / step).ceil() as usize).clamp(MIN_CIRCLE_SEGMENTS, MAX_CIRCLE_SEGMENTS)
+    }
+
+    //line that honours the width and antialiasing, plain one pixel lines stay on the fast path
+    pub fn segment(&mut self, a: Vector2, b: Vector2) {
+        if self.line_width <= 1.0 && !self.antialias {
+            self.line(a.x as i32, a.y as i32, b.x as i32, b.y as i32);
+            return;
+        }
+
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let length = dx.hypot(dy);
+
+        if length < f64::EPSILON {
+            return;
+        }
+
+        let normal = Vector2::new(-dy / length, dx / length);
+        let mut vertices = vec![];
+
+        if self.antialias {
+            //thinner than a pixel fades out instead of getting thinner
+            let color = Color::RGBA(self.color.r, self.color.g, self.color.b, (self.color.a as f64 * self.line_width.min(1.0)) as u8);
+            let core = ((self.line_width - FRINGE) / 2.0).max(0.0);
+            let edge = core + FRINGE;
+
+            if core > 0.0 {
+                let offset = scaled(normal, core);
+                quad(&mut vertices, a - offset, b - offset, b + offset, a + offset, color, color);
+            }
+
+            for side in [-1.0, 1.0] {
+                let inner = scaled(normal, core * side);
+                let outer = scaled(normal, edge * side);
+                quad(&mut vertices, a + inner, b + inner, b + outer, a + outer, color, transparent(color));
+            }
+        } else {
+            let offset = scaled(normal, self.line_width / 2.0);
+            quad(&mut vertices, a - offset, b - offset, b + offset, a + offset, self.color, self.color);
+        }
+
+        self.backend.fill_triangles(&vertices);
+    }
+
+    //thick lines get round joins so corners don't notch
+    pub fn polyline(&mut self, points: &[Vector2], closed: bool) {
+        if points.len() < 2 {
+            return;
+        }
+
+        for pair in points.windows(2) {
+            self.segment(pair[0], pair[1]);
+        }
+
+        if closed {
+            self.segment(points[points.len() - 1], points[0]);
+        }
+
+        if self.line_width > 2.0 {
+            let radius = self.line_width / 2.0;
+            let joins = if closed { points } else { &points[1..points.len() - 1] };
+
+            for join in joins {
+                self.fill_circle(*join, radius);
+            }
+        }
+    }
+
+    //colliders are convex, so a fan covers them
+    pub fn fill_convex_polygon(&mut self, points: &[Vector2]) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let color = self.color;
+        let mut vertices = vec![];
+
+        if !self.antialias {
+            for i in 1..points.len() - 1 {
+                vertices.extend([vertex(points[0], color), vertex(points[i], color), vertex(points[i + 1], color)]);
+            }
+
+            self.backend.fill_triangles(&vertices);
+            return;
+        }
+
+        //outward normals depend on the winding
+        let area: f64 = (0..points.len())
+            .map(|i| {
+                let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum();
+        let winding = if area < 0.0 { -1.0 } else { 1.0 };
+
+        let edge_normals: Vec<Vector2> = (0..points.len())
+            .map(|i| {
+                let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                let (dx, dy) = (b.x - a.x, b.y - a.y);
+                let length = dx.hypot(dy).max(f64::EPSILON);
+                Vector2::new(dy / length * winding, -dx / length * winding)
+            })
+            .collect();
+
+        //half a fringe either side of the real edge, mitred at the corners
+        let mut inner = vec![];
+        let mut outer = vec![];
+
+        for i in 0..points.len() {
+            let (before, after) = (edge_normals[(i + points.len() - 1) % points.len()], edge_normals[i]);
+            let mut miter = Vector2::new((before.x + after.x) / 2.0, (before.y + after.y) / 2.0);
+            let length_squared = miter.x * miter.x + miter.y * miter.y;
+
+            if length_squared > 0.000001 {
+                miter = scaled(miter, (1.0 / length_squared).min(100.0));
+            }
+
+            let offset = scaled(miter, FRINGE / 2.0);
+            inner.push(points[i] - offset);
+            outer.push(points[i] + offset);
+        }
+
+        for i in 1..inner.len() - 1 {
+            vertices.extend([vertex(inner[0], color), vertex(inner[i], color), vertex(inner[i + 1], color)]);
+        }
+
+        for i in 0..points.len() {
+            let next = (i + 1) % points.len();
+            quad(&mut vertices, inner[i], inner[next], outer[next], outer[i], color, transparent(color));
+        }
+
+        self.backend.fill_triangles(&vertices);
+    }
+
+    //sweep is signed, positive turns the same way as rotation
+    fn arc_points(center: Vector2, radius: f64, start: f64, sweep: f64) -> Vec<Vector2> {
+        let full = Self::circle_segments(radius);
+        let segments = ((full as f64 * sweep.abs() / (2.0 * PI)).ceil() as usize).max(1);
+
+        (0..=segments)
+            .map(|i| {
+                let angle = start + sweep * i as f64 / segments as f64;
+                Vector2::new(center.x + angle.cos() * radius, center.y + angle.sin() * radius)
+            })
+            .collect()
+    }
+
+    pub fn fill_circle(&mut self, center: Vector2, radius: f64) {
+        let mut points = Self::arc_points(center, radius, 0.0, 2.0 * PI);
+        points.pop();
+        self.fill_convex_polygon(&points);
+    }
+
+    //a full turn of sweep gives a circle outline
+    pub fn arc(&mut self, center: Vector2, radius: f64, start: f64, sweep: f64) {
+        let points = Self::arc_points(center, radius, start, sweep);
+        self.polyline(&points, false);
+    }
+
+    //filled head, the shaft stops at its base so wide lines don't poke through the tip
+    pub fn arrow(&mut self, from: Vector2, to: Vector2, head: f64) {
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let length = dx.hypot(dy);
+
+        if length < 1.0 {
+            self.segment(from, to);
+            return;
+        }
+
+        let direction = Vector2::new(dx / length, dy / length);
+        let head = head.max(self.line_width * 2.0).min(length / 2.0);
+        let base = to - scaled(direction, head);
+        let side = Vector2::new(-direction.y * head * 0.5, direction.x * head * 0.5);
+
+        self.segment(from, base);
+        self.fill_convex_polygon(&[to, base + side, base - side]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circle_segments_stay_within_tolerance() {
+        assert_eq!(Renderer::circle_segments(0.0), MIN_CIRCLE_SEGMENTS);
+        assert_eq!(Renderer::circle_segments(1.0), MIN_CIRCLE_SEGMENTS);
+        assert_eq!(Renderer::circle_segments(1e9), MAX_CIRCLE_SEGMENTS);
+
+        let mut last = 0;
+
+        for radius in [5.0, 20.0, 100.0, 1000.0] {
+            let segments = Renderer::circle_segments(radius);
+            //how far the middle of a chord is from the curve
+            let error = radius * (1.0 - (PI / segments as f64).cos());
+
+            assert!(error <= CIRCLE_TOLERANCE, "{} segments for radius {}", segments, radius);
+            assert!(segments >= last);
+            last = segments;
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use sdl2::render::Texture;
+
+use super::backend::{RenderBackend, TextHandle};
+
+//frames a string can go unused before it is freed
+const MAX_UNUSED_FRAMES: u64 = 120;
+//past this the least recently used strings go first
+const MAX_ENTRIES: usize = 1024;
+
+//whatever a backend keeps per string, freed when the string is evicted
+pub trait CacheEntry {
+    fn free(self);
+}
+
+impl CacheEntry for Texture {
+    fn free(self) {
+        //unsafe_textures means nothing frees these for us, the store is their only owner
+        unsafe {
+            self.destroy();
+        }
+    }
+}
+
+//rgba pixels and their size, dropping them is enough
+impl CacheEntry for (Vec<u8>, (u32, u32)) {
+    fn free(self) {}
+}
+
+//the strings a backend has uploaded, the renderer's cache decides when they go
+pub struct TextStore<T: CacheEntry> {
+    entries: HashMap<TextHandle, T>,
+    next: TextHandle,
+}
+
+impl<T: CacheEntry> TextStore<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            next: 0,
+        }
+    }
+
+    pub fn insert(&mut self, data: T) -> TextHandle {
+        let handle = self.next;
+        self.next += 1;
+        self.entries.insert(handle, data);
+        handle
+    }
+
+    pub fn get(&self, handle: TextHandle) -> Option<&T> {
+        self.entries.get(&handle)
+    }
+
+    pub fn remove(&mut self, handle: TextHandle) {
+        if let Some(data) = self.entries.remove(&handle) {
+            data.free();
+        }
+    }
+}
+
+impl<T: CacheEntry> Drop for TextStore<T> {
+    fn drop(&mut self) {
+        for (_, data) in self.entries.drain() {
+            data.free();
+        }
+    }
+}
+
+pub struct CachedText {
+    pub handle: TextHandle,
+    //rasterized size in pixels
+    pub size: (u32, u32),
+    last_used: u64,
+}
+
+pub struct TextCache {
+    //by font, then text, so a hit can look up with borrowed strings
+    fonts: HashMap<String, HashMap<String, CachedText>>,
+    frame: u64,
+}
+
+impl TextCache {
+    pub fn new() -> Self {
+        Self {
+            fonts: HashMap::new(),
+            frame: 0,
+        }
+    }
+
+    pub fn get(&mut self, font: &str, text: &str) -> Option<&CachedText> {
+        let entry = self.fonts.get_mut(font)?.get_mut(text)?;
+        entry.last_used = self.frame;
+        Some(entry)
+    }
+
+    pub fn insert(&mut self, font: &str, text: &str, handle: TextHandle, size: (u32, u32), backend: &mut dyn RenderBackend) -> &CachedText {
+        let entry = CachedText { handle, size, last_used: self.frame };
+        let texts = self.fonts.entry(font.to_string()).or_default();
+
+        if let Some(old) = texts.insert(text.to_string(), entry) {
+            backend.free_text(old.handle);
+        }
+
+        &texts[text]
+    }
+
+    pub fn end_frame(&mut self, backend: &mut dyn RenderBackend) {
+        let frame = self.frame;
+
+        for texts in self.fonts.values_mut() {
+            texts.retain(|_, entry| {
+                let stale = frame - entry.last_used > MAX_UNUSED_FRAMES;
+                if stale {
+                    backend.free_text(entry.handle);
+                }
+                !stale
+            });
+        }
+
+        let len: usize = self.fonts.values().map(HashMap::len).sum();
+
+        if len > MAX_ENTRIES {
+            let mut by_age: Vec<(u64, String, String)> = self.fonts
+                .iter()
+                .flat_map(|(font, texts)| texts.iter().map(move |(text, entry)| (entry.last_used, font.clone(), text.clone())))
+                .collect();
+            by_age.sort_by_key(|(last_used, _, _)| *last_used);
+
+            for (_, font, text) in by_age.into_iter().take(len - MAX_ENTRIES) {
+                backend.free_text(self.fonts.get_mut(&font).unwrap().remove(&text).unwrap().handle);
+            }
+        }
+
+        self.frame += 1;
+    }
+
+    //only strings drawn with that face are stale
+    pub fn remove_font(&mut self, font: &str, backend: &mut dyn RenderBackend) {
+        for (_, entry) in self.fonts.remove(font).into_iter().flatten() {
+            backend.free_text(entry.handle);
+        }
+    }
+}
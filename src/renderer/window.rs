@@ -0,0 +1,139 @@
+use sdl2::{pixels::{Color, PixelFormatEnum}, rect::{Point, Rect}, render::{BlendMode, Canvas, Texture, TextureCreator}, surface::Surface, sys, video::{Window, WindowContext}};
+
+use super::{backend::{RenderBackend, TextHandle, Vertex}, text_cache::TextStore};
+
+//hardware accelerated sdl window
+pub struct WindowBackend {
+    //dropped first, its textures belong to the canvas
+    texts: TextStore<Texture>,
+    canvas: Canvas<Window>,
+    texture_creator: TextureCreator<WindowContext>,
+}
+
+impl WindowBackend {
+    pub fn new(width: u32, height: u32, vsync: bool) -> Self {
+        //otherwise windows stretches the whole window and the dpi we scale by is applied twice
+        sdl2::hint::set("SDL_WINDOWS_DPI_AWARENESS", "permonitorv2");
+
+        let sdl_context = sdl2::init().unwrap();
+       
+        // canvas
+        let video_subsystem = sdl_context.video().unwrap();
+        
+        let window = video_subsystem
+            .window("SDL2", width, height)
+            .position_centered()
+            .resizable()
+            .build()
+            .map_err(|e| e.to_string())
+            .unwrap();
+        
+        let mut canvas_builder = window
+            .into_canvas()
+            .accelerated();
+
+        if vsync {
+            canvas_builder = canvas_builder.present_vsync();
+        }
+
+        let mut canvas = canvas_builder
+            .build()
+            .map_err(|e| e.to_string())
+            .unwrap();
+
+        canvas.set_blend_mode(BlendMode::Blend);
+       
+        // text
+        let texture_creator = canvas.texture_creator();
+
+        Self {
+            texture_creator,
+            texts: TextStore::new(),
+            canvas,
+        }
+    }
+}
+
+impl RenderBackend for WindowBackend {
+    //in pixels, which can differ from the window's own units on high dpi displays
+    fn size(&self) -> (u32, u32) {
+        self.canvas.output_size().unwrap()
+    }
+
+    fn set_color(&mut self, color: Color) {
+        self.canvas.set_draw_color(color);
+    }
+
+    fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        self.canvas.fill_rect(Rect::new(x, y, width as u32, height as u32)).unwrap();
+    }
+
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        self.canvas.draw_line(Point::new(x1, y1), Point::new(x2, y2)).unwrap();
+    }
+
+    fn outline_rect(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        self.canvas.draw_rect(Rect::new(x, y, width as u32, height as u32)).unwrap();
+    }
+
+    fn fill_triangles(&mut self, vertices: &[Vertex]) {
+        let vertices: Vec<sys::SDL_Vertex> = vertices
+            .iter()
+            .map(|vertex| sys::SDL_Vertex {
+                position: sys::SDL_FPoint { x: vertex.x, y: vertex.y },
+                color: sys::SDL_Color { r: vertex.color.r, g: vertex.color.g, b: vertex.color.b, a: vertex.color.a },
+                tex_coord: sys::SDL_FPoint { x: 0.0, y: 0.0 },
+            })
+            .collect();
+
+        //the sdl2 crate has no safe wrapper for geometry
+        unsafe {
+            sys::SDL_RenderGeometry(
+                self.canvas.raw(),
+                std::ptr::null_mut(),
+                vertices.as_ptr(),
+                vertices.len() as i32,
+                std::ptr::null(),
+                0,
+            );
+        }
+    }
+
+    fn dpi_scale(&self) -> f64 {
+        let window = self.canvas.window();
+
+        window
+            .display_index()
+            .and_then(|index| window.subsystem().display_dpi(index))
+            //quarter steps, odd reported dpis shouldn't give blurry text
+            .map(|(_, horizontal, _)| (horizontal as f64 / 96.0 * 4.0).round() / 4.0)
+            .unwrap_or(1.0)
+            .max(1.0)
+    }
+
+    fn upload_text(&mut self, surface: &Surface) -> Result<TextHandle, String> {
+        let texture = self.texture_creator
+            .create_texture_from_surface(surface)
+            .map_err(|e| e.to_string())?;
+
+        Ok(self.texts.insert(texture))
+    }
+
+    fn draw_text(&mut self, text: TextHandle, x: i32, y: i32, width: u32, height: u32) {
+        if let Some(texture) = self.texts.get(text) {
+            self.canvas.copy(texture, None, Rect::new(x, y, width, height)).unwrap();
+        }
+    }
+
+    fn free_text(&mut self, text: TextHandle) {
+        self.texts.remove(text);
+    }
+
+    fn read_pixels(&mut self) -> Result<Vec<u8>, String> {
+        self.canvas.read_pixels(None, PixelFormatEnum::RGBA32)
+    }
+
+    fn present(&mut self) {
+        self.canvas.present();
+    }
+}
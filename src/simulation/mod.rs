@@ -0,0 +1,228 @@
+pub mod timeline;
+
+use chsl::physics::{bounding_box::BoundingBox, world::PhysicsWorld};
+use serde::{Deserialize, Serialize};
+use timeline::Timeline;
+
+//most steps we'll take in one frame per unit of time scale before dropping time, stops a slow frame from snowballing
+const MAX_STEPS_PER_FRAME: usize = 8;
+
+const MIN_TIME_SCALE: f64 = 0.01;
+const MAX_TIME_SCALE: f64 = 10.0;
+
+//ten seconds of history at the default step size
+const TIMELINE_CAPACITY: usize = 600;
+
+//what a recording needs to carry on stepping exactly like the run it came from, besides the world
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationState {
+    pub paused: bool,
+    pub step_size: f64,
+    pub substeps: usize,
+    pub iterations: usize,
+    pub time_scale: f64,
+    pub accumulator: f64,
+    pub steps_taken: u64,
+}
+
+pub struct Simulation {
+    pub paused: bool,
+    pub step_size: f64,
+    pub substeps: usize,
+    pub iterations: usize,
+    time_scale: f64,
+    accumulator: f64,
+    queued_steps: usize,
+    steps_taken: u64,
+    //the last update hit the step cap and dropped time, so the sim is running slower than asked
+    falling_behind: bool,
+    pub timeline: Timeline,
+    rewind_to: Option<usize>,
+}
+
+impl Simulation {
+    pub fn new() -> Self {
+        Self {
+            paused: false,
+            step_size: 1.0 / 60.0,
+            substeps: 1,
+            iterations: 1,
+            time_scale: 1.0,
+            accumulator: 0.0,
+            queued_steps: 0,
+            steps_taken: 0,
+            falling_behind: false,
+            timeline: Timeline::new(TIMELINE_CAPACITY),
+            rewind_to: None,
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    //only does anything while paused, a running sim steps itself
+    pub fn step(&mut self, steps: usize) {
+        self.queued_steps += steps;
+    }
+
+    //swaps the world for a recorded snapshot on the next update, pausing so it can be inspected
+    pub fn rewind(&mut self, index: usize) {
+        self.paused = true;
+        self.rewind_to = Some(index);
+    }
+
+    pub fn time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f64) {
+        self.time_scale = time_scale.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+    }
+
+    pub fn steps_taken(&self) -> u64 {
+        self.steps_taken
+    }
+
+    pub fn falling_behind(&self) -> bool {
+        self.falling_behind
+    }
+
+    pub fn state(&self) -> SimulationState {
+        SimulationState {
+            paused: self.paused,
+            step_size: self.step_size,
+            substeps: self.substeps,
+            iterations: self.iterations,
+            time_scale: self.time_scale,
+            accumulator: self.accumulator,
+            steps_taken: self.steps_taken,
+        }
+    }
+
+    //the timeline belongs to whatever world was loaded before, so it's dropped
+    pub fn restore(&mut self, state: &SimulationState) {
+        self.paused = state.paused;
+        self.step_size = state.step_size;
+        self.substeps = state.substeps;
+        self.iterations = state.iterations;
+        self.set_time_scale(state.time_scale);
+        self.accumulator = state.accumulator;
+        self.steps_taken = state.steps_taken;
+        self.queued_steps = 0;
+        self.falling_behind = false;
+        self.rewind_to = None;
+        self.timeline.clear();
+    }
+
+    pub fn update(&mut self, physics_world: &mut PhysicsWorld, delta_time: f64) -> usize {
+        if let Some(index) = self.rewind_to.take() {
+            if let Some((step, snapshot)) = self.timeline.restore(index) {
+                *physics_world = snapshot;
+                self.steps_taken = step;
+            }
+        }
+
+        if self.timeline.is_empty() {
+            self.timeline.record(self.steps_taken, physics_world);
+        }
+
+        let steps = if self.paused {
+            self.accumulator = 0.0;
+            self.falling_behind = false;
+            std::mem::take(&mut self.queued_steps)
+        } else {
+            self.queued_steps = 0;
+            //only sim time is scaled, the ui keeps using the real frame delta
+            self.accumulator += delta_time * self.time_scale;
+
+            let steps = (self.accumulator / self.step_size) as usize;
+            self.accumulator -= steps as f64 * self.step_size;
+
+            //a faster time scale needs more steps per frame, so the cap grows with it
+            let max_steps = MAX_STEPS_PER_FRAME * self.time_scale.ceil() as usize;
+
+            self.falling_behind = steps > max_steps;
+
+            if self.falling_behind {
+                self.accumulator = 0.0;
+                max_steps
+            } else {
+                steps
+            }
+        };
+
+        for _ in 0..steps {
+            self.fixed_step(physics_world);
+        }
+
+        steps
+    }
+
+    fn fixed_step(&mut self, physics_world: &mut PhysicsWorld) {
+        let substeps = self.substeps.max(1);
+        let dt = self.step_size / substeps as f64;
+
+        for _ in 0..substeps {
+            physics_world.update(dt, self.iterations.max(1));
+        }
+
+        self.steps_taken += 1;
+        self.timeline.record(self.steps_taken, physics_world);
+    }
+}
+
+//the world only takes its bounds when it's made, so move everything into a new one
+//only the bodies and constraints are carried over, anything else the world keeps for itself starts fresh
+pub fn rebuild_with_bounds(physics_world: &mut PhysicsWorld, bounds: BoundingBox) {
+    let bodies = std::mem::take(physics_world.all_bodies());
+    let constraints = std::mem::take(physics_world.all_constraints());
+
+    *physics_world = PhysicsWorld::new(bounds);
+
+    for (id, body) in bodies {
+        physics_world.add_body(&id, body);
+    }
+
+    for (id, constraint) in constraints {
+        physics_world.add_constraint(&id, constraint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> PhysicsWorld {
+        PhysicsWorld::new(BoundingBox { x: 0.0, y: 0.0, width: 1000.0, height: 1000.0 })
+    }
+
+    #[test]
+    fn max_time_scale_is_reached() {
+        let mut physics_world = world();
+        let mut simulation = Simulation::new();
+        //powers of two so the accumulator is exact
+        simulation.step_size = 0.25;
+        simulation.set_time_scale(MAX_TIME_SCALE);
+
+        for _ in 0..6 {
+            assert_eq!(simulation.update(&mut physics_world, 0.25), 10);
+            assert!(!simulation.falling_behind());
+        }
+
+        assert_eq!(simulation.steps_taken(), 60);
+    }
+
+    #[test]
+    fn slow_frame_drops_time() {
+        let mut physics_world = world();
+        let mut simulation = Simulation::new();
+        simulation.step_size = 0.25;
+
+        assert_eq!(simulation.update(&mut physics_world, 100.0), MAX_STEPS_PER_FRAME);
+        assert!(simulation.falling_behind());
+
+        assert_eq!(simulation.update(&mut physics_world, 0.25), 1);
+        assert!(!simulation.falling_behind());
+    }
+}
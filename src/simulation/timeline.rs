@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+use chsl::physics::world::PhysicsWorld;
+use rmp_serde::{Deserializer, Serializer};
+use serde::{Deserialize, Serialize};
+
+struct Snapshot {
+    step: u64,
+    data: Vec<u8>,
+}
+
+pub struct Timeline {
+    snapshots: VecDeque<Snapshot>,
+    capacity: usize,
+}
+
+impl Timeline {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn record(&mut self, step: u64, physics_world: &PhysicsWorld) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        //stepping from a rewound point branches off, so the old future goes
+        while self.snapshots.back().is_some_and(|snapshot| snapshot.step >= step) {
+            self.snapshots.pop_back();
+        }
+
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+
+        let mut data = Vec::new();
+        physics_world.serialize(&mut Serializer::new(&mut data)).unwrap();
+
+        self.snapshots.push_back(Snapshot { step, data });
+    }
+
+    pub fn restore(&self, index: usize) -> Option<(u64, PhysicsWorld)> {
+        let snapshot = self.snapshots.get(index)?;
+        let mut de = Deserializer::new(&snapshot.data[..]);
+
+        PhysicsWorld::deserialize(&mut de)
+            .ok()
+            .map(|physics_world| (snapshot.step, physics_world))
+    }
+
+    pub fn index_of(&self, step: u64) -> Option<usize> {
+        self.snapshots.iter().position(|snapshot| snapshot.step == step)
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+}
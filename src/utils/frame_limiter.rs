@@ -0,0 +1,77 @@
+use std::{thread, time::{Duration, Instant}};
+
+//sleeping overshoots by about a millisecond, so the rest is spun out
+const SPIN_MARGIN: Duration = Duration::from_millis(1);
+
+pub struct FrameLimiter {
+    //None leaves the frame rate uncapped
+    pub target_fps: Option<f64>,
+    pub idle_fps: f64,
+    pub idle: bool,
+    frame_start: Instant,
+}
+
+impl FrameLimiter {
+    pub fn new(target_fps: Option<f64>, idle_fps: f64) -> Self {
+        Self {
+            target_fps,
+            idle_fps,
+            idle: false,
+            frame_start: Instant::now(),
+        }
+    }
+
+    fn frame_time(&self) -> Option<Duration> {
+        let fps = if self.idle {
+            Some(self.target_fps.map_or(self.idle_fps, |fps| fps.min(self.idle_fps)))
+        } else {
+            self.target_fps
+        };
+
+        fps.filter(|fps| *fps > 0.0).map(|fps| Duration::from_secs_f64(1.0 / fps))
+    }
+
+    //waits out the rest of the frame and returns the time since the last one
+    pub fn next_frame(&mut self) -> f64 {
+        if let Some(frame_time) = self.frame_time() {
+            let end = self.frame_start + frame_time;
+            let now = Instant::now();
+
+            if end > now + SPIN_MARGIN {
+                thread::sleep(end - now - SPIN_MARGIN);
+            }
+
+            while Instant::now() < end {
+                std::hint::spin_loop();
+            }
+        }
+
+        let now = Instant::now();
+        let dt = now - self.frame_start;
+        self.frame_start = now;
+
+        dt.as_secs_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_time(target_fps: Option<f64>, idle_fps: f64, idle: bool) -> Option<Duration> {
+        let mut limiter = FrameLimiter::new(target_fps, idle_fps);
+        limiter.idle = idle;
+        limiter.frame_time()
+    }
+
+    #[test]
+    fn idle_uses_the_slower_rate() {
+        let fps = |fps: f64| Some(Duration::from_secs_f64(1.0 / fps));
+
+        assert_eq!(frame_time(Some(60.0), 15.0, false), fps(60.0));
+        assert_eq!(frame_time(Some(60.0), 15.0, true), fps(15.0));
+        assert_eq!(frame_time(Some(10.0), 15.0, true), fps(10.0));
+        assert_eq!(frame_time(None, 15.0, false), None);
+        assert_eq!(frame_time(None, 15.0, true), fps(15.0));
+    }
+}
@@ -0,0 +1,123 @@
+use std::{fs::{self, File}, io::{BufWriter, Write}, path::Path, sync::mpsc::{self, SyncSender}, thread::{self, JoinHandle}};
+
+use gif::{Encoder, Frame, Repeat};
+
+use crate::{renderer::Renderer, utils::screenshot::save_png};
+
+//1 is best, 30 is fastest, past ~10 the colours start to band
+const GIF_QUANTIZE_SPEED: i32 = 10;
+//frames waiting for the encoder, once it's full capturing waits for it to catch up
+const GIF_QUEUE_FRAMES: usize = 8;
+
+//pixels and delay of one kept frame
+type GifFrame = (Vec<u8>, u16);
+
+enum Output {
+    //numbered pngs in a folder
+    Pngs(String),
+    //quantizing takes far longer than a frame, so it happens on its own thread
+    Gif {
+        frames: SyncSender<GifFrame>,
+        worker: JoinHandle<Result<(), String>>,
+    },
+}
+
+pub struct FrameRecorder {
+    output: Output,
+    size: (u32, u32),
+    //only every nth rendered frame is kept
+    every: usize,
+    frame: usize,
+    written: usize,
+    //time since the last kept frame, becomes the gif delay
+    elapsed: f64,
+}
+
+impl FrameRecorder {
+    //a .gif path is encoded directly, anything else is a folder of pngs
+    pub fn new(path: &str, size: (u32, u32), every: usize) -> Result<Self, String> {
+        let output = if path.ends_with(".gif") {
+            let file = File::create(path).map_err(|e| e.to_string())?;
+
+            let mut encoder = Encoder::new(BufWriter::new(file), size.0 as u16, size.1 as u16, &[])
+                .map_err(|e| e.to_string())?;
+            encoder.set_repeat(Repeat::Infinite).map_err(|e| e.to_string())?;
+
+            let (frames, queue) = mpsc::sync_channel::<GifFrame>(GIF_QUEUE_FRAMES);
+
+            let worker = thread::spawn(move || {
+                for (mut pixels, delay) in queue {
+                    let mut frame = Frame::from_rgba_speed(size.0 as u16, size.1 as u16, &mut pixels, GIF_QUANTIZE_SPEED);
+                    frame.delay = delay;
+                    encoder.write_frame(&frame).map_err(|e| e.to_string())?;
+                }
+
+                encoder
+                    .into_inner()
+                    .and_then(|mut writer| writer.flush())
+                    .map_err(|e| e.to_string())
+            });
+
+            Output::Gif { frames, worker }
+        } else {
+            fs::create_dir_all(path).map_err(|e| e.to_string())?;
+            Output::Pngs(path.to_string())
+        };
+
+        Ok(Self {
+            output,
+            size,
+            every: every.max(1),
+            frame: 0,
+            written: 0,
+            elapsed: 0.0,
+        })
+    }
+
+    //call once per rendered frame, before the renderer presents
+    pub fn capture(&mut self, renderer: &mut Renderer, delta_time: f64) -> Result<(), String> {
+        let keep = self.frame.is_multiple_of(self.every);
+        self.elapsed += delta_time;
+        self.frame += 1;
+
+        if !keep {
+            return Ok(());
+        }
+
+        let pixels = renderer.read_pixels()?;
+
+        match &mut self.output {
+            Output::Pngs(folder) => {
+                let path = Path::new(folder).join(format!("frame_{:05}.png", self.written));
+                save_png(&path.to_string_lossy(), renderer.size, &pixels)?;
+            }
+            Output::Gif { .. } if renderer.size != self.size => {
+                return Err("the window was resized, every gif frame has to be the same size".to_string());
+            }
+            Output::Gif { frames, .. } => {
+                //gif delays are in hundredths of a second
+                let delay = (self.elapsed * 100.0).round().clamp(1.0, u16::MAX as f64) as u16;
+
+                //the worker only hangs up when encoding failed, finish has the reason
+                if frames.send((pixels, delay)).is_err() {
+                    return Err("the gif encoder stopped".to_string());
+                }
+            }
+        }
+
+        self.written += 1;
+        self.elapsed = 0.0;
+
+        Ok(())
+    }
+
+    //waits for the queued gif frames and writes the trailer, returns how many frames were kept
+    pub fn finish(self) -> Result<usize, String> {
+        if let Output::Gif { frames, worker } = self.output {
+            drop(frames);
+            worker.join().map_err(|_| "the gif encoder panicked".to_string())??;
+        }
+
+        Ok(self.written)
+    }
+}
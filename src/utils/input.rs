@@ -1,5 +1,7 @@
-use std::collections::HashSet;
-use sdl2::{event::Event, keyboard::Keycode, mouse::MouseButton, EventPump};
+use std::{cell::Cell, collections::HashSet};
+use sdl2::{event::{Event, WindowEvent}, keyboard::Keycode, mouse::MouseButton, EventPump};
+
+use super::input_recording::{InputFrame, InputRecording};
 
 pub struct Input {
     events: EventPump,
@@ -14,19 +16,62 @@ pub struct Input {
     held_mouse: HashSet<MouseButton>, 
     mouse_x: i32,
     mouse_y: i32,
+    wheel: i32,
 
     close: bool,
+    any_events: bool,
+    //resized or moved to another display this frame
+    window_changed: bool,
+    display_changed: bool,
+    //set by text inputs as they render, read back the frame after
+    text_focus: Cell<bool>,
+    text_focused: bool,
+
+    recording: Option<InputRecording>,
+    playback: Option<std::vec::IntoIter<InputFrame>>,
 }
 
 impl Input {
-    pub fn update(&mut self) {
+    //returns the delta time the frame should use, which is the recorded one during playback
+    pub fn update(&mut self, delta_time: f64) -> f64 {
+        self.poll_events();
+
+        //live events are dropped while replaying, closing the window still works though
+        if let Some(playback) = &mut self.playback {
+            match playback.next() {
+                Some(frame) => {
+                    self.apply_frame(&frame);
+                    return frame.delta_time;
+                }
+                None => {
+                    self.playback = None;
+                }
+            }
+        }
+
+        if let Some(mut recording) = self.recording.take() {
+            recording.frames.push(self.frame(delta_time));
+            self.recording = Some(recording);
+        }
+
+        delta_time
+    }
+
+    fn poll_events(&mut self) {
         self.just_pressed_keys.clear();
         self.just_pressed_mouse.clear();
         self.just_released_keys.clear();
         self.just_released_mouse.clear();
         self.just_pressed_text = String::new();
+        self.any_events = false;
+        self.window_changed = false;
+        self.display_changed = false;
+        self.text_focused = self.text_focus.take();
+        self.wheel = 0;
 
         for e in self.events.poll_iter() {
+            self.any_events = true;
+
             match e {
                 Event::MouseButtonDown { mouse_btn, x, y, .. } => {
                     self.just_pressed_mouse.insert(mouse_btn);
@@ -47,6 +92,9 @@ impl Input {
                     self.mouse_x = x;
                     self.mouse_y = y;
                 }
+                Event::MouseWheel { y, .. } => {
+                    self.wheel += y;
+                }
                 Event::KeyDown { keycode, .. } => {
                     self.just_pressed_keys.insert(keycode.unwrap());
                     self.held_keys.insert(keycode.unwrap());
@@ -58,11 +106,74 @@ impl Input {
                 Event::Quit { .. } => {
                     self.close = true;
                 }
+                Event::Window { win_event: WindowEvent::SizeChanged(..), .. } => {
+                    self.window_changed = true;
+                }
+                Event::Window { win_event: WindowEvent::DisplayChanged(..), .. } => {
+                    self.window_changed = true;
+                    self.display_changed = true;
+                }
                 _ => {}
             }
         };
     }
     
+    fn frame(&self, delta_time: f64) -> InputFrame {
+        InputFrame {
+            delta_time,
+            held_keys: self.held_keys.iter().map(|key| key.into_i32()).collect(),
+            just_pressed_keys: self.just_pressed_keys.iter().map(|key| key.into_i32()).collect(),
+            just_released_keys: self.just_released_keys.iter().map(|key| key.into_i32()).collect(),
+            just_pressed_text: self.just_pressed_text.clone(),
+            held_mouse: self.held_mouse.iter().map(|button| *button as u8).collect(),
+            just_pressed_mouse: self.just_pressed_mouse.iter().map(|button| *button as u8).collect(),
+            just_released_mouse: self.just_released_mouse.iter().map(|button| *button as u8).collect(),
+            mouse_x: self.mouse_x,
+            mouse_y: self.mouse_y,
+            wheel: self.wheel,
+        }
+    }
+
+    fn apply_frame(&mut self, frame: &InputFrame) {
+        let keys = |codes: &Vec<i32>| codes.iter().filter_map(|code| Keycode::from_i32(*code)).collect();
+        let buttons = |codes: &Vec<u8>| codes.iter().map(|code| MouseButton::from_ll(*code)).collect();
+
+        self.held_keys = keys(&frame.held_keys);
+        self.just_pressed_keys = keys(&frame.just_pressed_keys);
+        self.just_released_keys = keys(&frame.just_released_keys);
+        self.just_pressed_text = frame.just_pressed_text.clone();
+        self.held_mouse = buttons(&frame.held_mouse);
+        self.just_pressed_mouse = buttons(&frame.just_pressed_mouse);
+        self.just_released_mouse = buttons(&frame.just_released_mouse);
+        self.mouse_x = frame.mouse_x;
+        self.mouse_y = frame.mouse_y;
+        self.wheel = frame.wheel;
+    }
+
+    pub fn start_recording(&mut self, recording: InputRecording) {
+        self.recording = Some(recording);
+    }
+
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        let mut recording = self.recording.take()?;
+        //this frame has the click that stopped the recording, replaying it would start a new one
+        recording.frames.pop();
+        Some(recording)
+    }
+
+    pub fn recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn start_playback(&mut self, recording: InputRecording) {
+        self.recording = None;
+        self.playback = Some(recording.frames.into_iter());
+    }
+
+    pub fn playing_back(&self) -> bool {
+        self.playback.is_some()
+    }
+
     pub fn just_pressed(&self, key: &Keycode) -> bool {
         self.just_pressed_keys.contains(key)
     }
@@ -95,6 +206,33 @@ impl Input {
         (self.mouse_x, self.mouse_y)
     }
 
+    //scrolled notches this frame, positive is away from the user
+    pub fn wheel(&self) -> i32 {
+        self.wheel
+    }
+
+    pub fn any_events(&self) -> bool {
+        self.any_events
+    }
+
+    pub fn window_changed(&self) -> bool {
+        self.window_changed
+    }
+
+    //the dpi could be different now
+    pub fn display_changed(&self) -> bool {
+        self.display_changed
+    }
+
+    pub fn set_text_focus(&self) {
+        self.text_focus.set(true);
+    }
+
+    //keyboard shortcuts should be skipped while this is true, the keys are going into a field
+    pub fn text_focused(&self) -> bool {
+        self.text_focused || self.text_focus.get()
+    }
+
     pub fn close_button(&self) -> bool {
         self.close
     }
@@ -109,9 +247,17 @@ impl Input {
             just_pressed_text: String::new(),
             mouse_y: 0,
             mouse_x: 0,
+            wheel: 0,
             close: false,
+            any_events: false,
+            window_changed: false,
+            display_changed: false,
+            text_focus: Cell::new(false),
+            text_focused: false,
             held_mouse: HashSet::new(),
             events: event_pump,
+            recording: None,
+            playback: None,
         }
     }
 }
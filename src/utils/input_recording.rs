@@ -0,0 +1,55 @@
+use std::fs;
+
+use rmp_serde::{Deserializer, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::{renderer::camera::Camera, simulation::SimulationState};
+
+//everything Input knows about one frame, keys and buttons stored as their sdl values
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct InputFrame {
+    pub delta_time: f64,
+    pub held_keys: Vec<i32>,
+    pub just_pressed_keys: Vec<i32>,
+    pub just_released_keys: Vec<i32>,
+    pub just_pressed_text: String,
+    pub held_mouse: Vec<u8>,
+    pub just_pressed_mouse: Vec<u8>,
+    pub just_released_mouse: Vec<u8>,
+    pub mouse_x: i32,
+    pub mouse_y: i32,
+    //added after the first recordings were made
+    #[serde(default)]
+    pub wheel: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct InputRecording {
+    //serialized world the recording started from
+    pub world: Vec<u8>,
+    //the frames only replay the same way from the same simulation settings and view
+    pub simulation: SimulationState,
+    pub camera: Camera,
+    //mouse positions only land on the same panels and bodies in the same window at the same scale
+    pub window_size: (u32, u32),
+    pub ui_scale: f64,
+    pub frames: Vec<InputFrame>,
+}
+
+impl InputRecording {
+    pub fn new(world: Vec<u8>, simulation: SimulationState, camera: Camera, window_size: (u32, u32), ui_scale: f64) -> Self {
+        Self { world, simulation, camera, window_size, ui_scale, frames: vec![] }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let mut buf = Vec::new();
+        self.serialize(&mut Serializer::new(&mut buf)).map_err(|e| e.to_string())?;
+        fs::write(path, buf).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = fs::read(path).map_err(|e| e.to_string())?;
+        let mut de = Deserializer::new(&contents[..]);
+        InputRecording::deserialize(&mut de).map_err(|e| e.to_string())
+    }
+}
@@ -1,28 +1,185 @@
+pub mod frame_limiter;
+pub mod frame_recorder;
 pub mod input;
+pub mod input_recording;
+pub mod scene;
+pub mod screenshot;
 
-use std::{collections::HashMap, time::{Duration, SystemTime, UNIX_EPOCH}};
+use std::{cell::Cell, collections::{BTreeMap, HashMap, VecDeque}, fs::File, io::{BufWriter, Write}, sync::atomic::{AtomicU64, Ordering}, time::{Duration, Instant}};
+
+//frames of timings kept per scope
+const HISTORY: usize = 240;
+
+//stops a forgotten recording from eating all the memory
+const MAX_TRACE_EVENTS: usize = 1_000_000;
+
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+//small stable ids per thread, std only gives opaque ones
+fn thread_id() -> u64 {
+    THREAD_ID.with(|id| {
+        if id.get() == 0 {
+            id.set(NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed));
+        }
+        id.get()
+    })
+}
+
+struct TraceEvent {
+    name: &'static str,
+    begin: bool,
+    //microseconds since the timer was made
+    timestamp: f64,
+    thread: u64,
+}
+
+//all in milliseconds
+pub struct ScopeStats {
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
 
 pub struct TimerUtil {
-    timers: HashMap<&'static str, Duration>
+    open: Vec<(&'static str, Instant)>,
+    frame: HashMap<String, Duration>,
+    //keyed by path, e.g. "frame/UI", sorted so children follow their parent
+    history: BTreeMap<String, VecDeque<f64>>,
+    pub log: bool,
+    epoch: Instant,
+    trace: Option<Vec<TraceEvent>>,
 }
 
 impl TimerUtil {
     pub fn new() -> Self {
         Self {
-            timers: HashMap::new()
+            open: vec![],
+            frame: HashMap::new(),
+            history: BTreeMap::new(),
+            log: false,
+            epoch: Instant::now(),
+            trace: None,
         }
     }
+    //scopes nest, anything started before this one is stopped is its child
     pub fn start(&mut self, name: &'static str) {
-        self.timers.insert(name, SystemTime::now().duration_since(UNIX_EPOCH).unwrap());
+        let now = Instant::now();
+        self.trace_event(name, true, now);
+        self.open.push((name, now));
     }
     pub fn stop(&mut self, name: &'static str) -> Duration {
-        let start = self.timers.get(name).unwrap().clone(); 
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-        self.timers.remove(name);
+        let path = self.path();
+        let (open, start) = self.open.pop().unwrap();
+        debug_assert_eq!(open, name, "timer scopes stopped out of order");
+
+        let now = Instant::now();
+        let elapsed = now - start;
+        *self.frame.entry(path).or_default() += elapsed;
+        self.trace_event(name, false, now);
+
+        elapsed
+    }
+    fn trace_event(&mut self, name: &'static str, begin: bool, at: Instant) {
+        if let Some(trace) = &mut self.trace {
+            if trace.len() < MAX_TRACE_EVENTS {
+                trace.push(TraceEvent {
+                    name,
+                    begin,
+                    timestamp: (at - self.epoch).as_secs_f64() * 1_000_000.0,
+                    thread: thread_id(),
+                });
+            }
+        }
+    }
+    pub fn start_trace(&mut self) {
+        self.trace = Some(vec![]);
+    }
+    pub fn tracing(&self) -> bool {
+        self.trace.is_some()
+    }
+    //writes everything since start_trace in chrome's trace event format
+    pub fn stop_trace(&mut self, path: &str) -> Result<(), String> {
+        let Some(trace) = self.trace.take() else {
+            return Err("no trace is being recorded".to_string());
+        };
+
+        let write = || -> std::io::Result<()> {
+            let mut out = BufWriter::new(File::create(path)?);
+
+            writeln!(out, "{{\"displayTimeUnit\":\"ms\",\"traceEvents\":[")?;
+
+            for (i, event) in trace.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{{\"name\":\"{}\",\"ph\":\"{}\",\"ts\":{:.3},\"pid\":1,\"tid\":{}}}{}",
+                    event.name.replace('\\', "\\\\").replace('"', "\\\""),
+                    if event.begin { "B" } else { "E" },
+                    event.timestamp,
+                    event.thread,
+                    if i + 1 < trace.len() { "," } else { "" },
+                )?;
+            }
+
+            writeln!(out, "]}}")?;
+            out.flush()
+        };
+
+        write().map_err(|e| e.to_string())
+    }
+    fn path(&self) -> String {
+        self.open.iter().map(|(name, _)| *name).collect::<Vec<_>>().join("/")
+    }
+    pub fn end_frame(&mut self) {
+        for path in self.frame.keys() {
+            if !self.history.contains_key(path) {
+                self.history.insert(path.clone(), VecDeque::with_capacity(HISTORY));
+            }
+        }
 
-        now - start
+        //scopes that didn't run this frame took no time
+        for (path, history) in self.history.iter_mut() {
+            if history.len() == HISTORY {
+                history.pop_front();
+            }
+
+            let elapsed = self.frame.get(path).copied().unwrap_or_default();
+            history.push_back(elapsed.as_secs_f64() * 1000.0);
+
+            if self.log {
+                println!("{}: {:?}", path, elapsed);
+            }
+        }
+
+        self.frame.clear();
+    }
+    pub fn scopes(&self) -> impl Iterator<Item = &String> {
+        self.history.keys()
     }
-    pub fn stop_log_secs(&mut self, name: &'static str) {
-        println!("{}: {:?}", name, self.stop(name))
+    pub fn history(&self, path: &str) -> Option<&VecDeque<f64>> {
+        self.history.get(path)
+    }
+    pub fn stats(&self, path: &str) -> Option<ScopeStats> {
+        let history = self.history.get(path)?;
+
+        let mut sorted: Vec<f64> = history.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let percentile = |p: f64| sorted[((sorted.len() - 1) as f64 * p).round() as usize];
+
+        Some(ScopeStats {
+            min: sorted[0],
+            avg: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            max: sorted[sorted.len() - 1],
+            p50: percentile(0.5),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+        })
     }
 }
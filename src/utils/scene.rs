@@ -0,0 +1,110 @@
+use std::{fs, path::Path};
+
+use chsl::physics::world::PhysicsWorld;
+use rmp_serde::{Deserializer, Serializer};
+use serde::{Deserialize, Serialize};
+
+//x, y, width, height
+pub type Bounds = (f64, f64, f64, f64);
+
+//chsl has no way to hand a world's bounds back, so a save keeps its own copy next to the world
+#[derive(Serialize)]
+struct SaveRef<'a> {
+    world: &'a PhysicsWorld,
+    //none if they weren't known when saving
+    bounds: Option<Bounds>,
+}
+
+#[derive(Deserialize)]
+struct Save {
+    world: PhysicsWorld,
+    bounds: Option<Bounds>,
+}
+
+pub fn save_world(physics_world: &PhysicsWorld, bounds: Option<Bounds>, path: &str) -> Result<(), String> {
+    fs::write(path, world_to_bytes(physics_world, bounds)?).map_err(|e| e.to_string())
+}
+
+pub fn load_world(path: &str) -> Result<(PhysicsWorld, Option<Bounds>), String> {
+    world_from_bytes(&fs::read(path).map_err(|e| e.to_string())?)
+}
+
+//what a save file holds, input recordings keep their starting world the same way
+pub fn world_to_bytes(physics_world: &PhysicsWorld, bounds: Option<Bounds>) -> Result<Vec<u8>, String> {
+    let mut buf = Vec::new();
+    SaveRef { world: physics_world, bounds }.serialize(&mut Serializer::new(&mut buf)).map_err(|e| e.to_string())?;
+    Ok(buf)
+}
+
+//saves from before the bounds were kept are just the world, they load without bounds
+pub fn world_from_bytes(bytes: &[u8]) -> Result<(PhysicsWorld, Option<Bounds>), String> {
+    if let Ok(save) = Save::deserialize(&mut Deserializer::new(bytes)) {
+        return Ok((save.world, save.bounds));
+    }
+
+    let world = PhysicsWorld::deserialize(&mut Deserializer::new(bytes)).map_err(|e| e.to_string())?;
+    Ok((world, None))
+}
+
+//saves/level.chsl gives saves/level_1.png, a folder gives saves/<fallback>_1.png, never overwrites
+//no extension names a new folder
+pub fn export_path(save_path: &str, fallback: &str, extension: &str) -> String {
+    let path = Path::new(save_path);
+
+    let base = if save_path.is_empty() || save_path.ends_with('/') || path.is_dir() {
+        path.join(fallback)
+    } else {
+        path.with_extension("")
+    };
+
+    (1..)
+        .map(|n| match extension {
+            "" => format!("{}_{}", base.display(), n),
+            _ => format!("{}_{}.{}", base.display(), n, extension),
+        })
+        .find(|candidate| !Path::new(candidate).exists())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chsl::physics::bounding_box::BoundingBox;
+
+    fn world() -> PhysicsWorld {
+        PhysicsWorld::new(BoundingBox { x: 0.0, y: 0.0, width: 1240.0, height: 880.0 })
+    }
+
+    #[test]
+    fn bounds_are_saved_with_the_world() {
+        let bytes = world_to_bytes(&world(), Some((0.0, 0.0, 1240.0, 880.0))).unwrap();
+        let (_, bounds) = world_from_bytes(&bytes).unwrap();
+
+        assert_eq!(bounds, Some((0.0, 0.0, 1240.0, 880.0)));
+    }
+
+    #[test]
+    fn old_saves_load_without_bounds() {
+        let mut bytes = Vec::new();
+        world().serialize(&mut Serializer::new(&mut bytes)).unwrap();
+        let (_, bounds) = world_from_bytes(&bytes).unwrap();
+
+        assert_eq!(bounds, None);
+    }
+
+    #[test]
+    fn export_paths_never_overwrite() {
+        let dir = std::env::temp_dir().join(format!("export_path_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let folder = format!("{}/", dir.display());
+
+        assert_eq!(export_path(&format!("{}level.chsl", folder), "shot", "png"), format!("{}level_1.png", folder));
+        assert_eq!(export_path(&folder, "shot", "png"), format!("{}shot_1.png", folder));
+
+        fs::write(dir.join("shot_1.png"), []).unwrap();
+        assert_eq!(export_path(&folder, "shot", "png"), format!("{}shot_2.png", folder));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
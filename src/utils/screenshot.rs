@@ -0,0 +1,33 @@
+use std::{fs::File, io::BufWriter};
+
+use crate::renderer::Renderer;
+
+pub struct Screenshot {
+    pub path: String,
+    //otherwise only the world and grid are captured
+    pub include_ui: bool,
+}
+
+impl Screenshot {
+    //without the ui the scene is drawn again off screen, the frame being shown keeps its panels
+    pub fn capture(&self, renderer: &mut Renderer, draw_scene: impl FnOnce(&mut Renderer)) -> Result<(), String> {
+        let pixels = if self.include_ui {
+            renderer.read_pixels()?
+        } else {
+            renderer.render_offscreen(draw_scene)?
+        };
+
+        save_png(&self.path, renderer.size, &pixels)
+    }
+}
+
+pub fn save_png(path: &str, size: (u32, u32), rgba: &[u8]) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| e.to_string())?;
+
+    let mut encoder = png::Encoder::new(BufWriter::new(file), size.0, size.1);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(rgba).map_err(|e| e.to_string())
+}